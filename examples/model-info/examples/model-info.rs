@@ -1,6 +1,6 @@
 use std::{env, process};
 
-use ort::{Session, TensorElementDataType, ValueType};
+use ort::{Dim, Session, TensorElementDataType, ValueType};
 
 fn display_element_type(t: TensorElementDataType) -> &'static str {
 	match t {
@@ -21,21 +21,27 @@ fn display_element_type(t: TensorElementDataType) -> &'static str {
 	}
 }
 
+fn display_dimensions(dimensions: &[Dim]) -> String {
+	dimensions
+		.iter()
+		.map(|dim| match dim {
+			Dim::Fixed(size) => size.to_string(),
+			Dim::Symbolic(name) => name.clone(),
+			Dim::Unknown => "dyn".to_string()
+		})
+		.collect::<Vec<_>>()
+		.join(", ")
+}
+
 fn display_value_type(value: &ValueType) -> String {
 	match value {
-		ValueType::Tensor { ty, dimensions } => {
-			format!(
-				"Tensor<{}>({})",
-				display_element_type(*ty),
-				dimensions
-					.iter()
-					.map(|c| if *c == -1 { "dyn".to_string() } else { c.to_string() })
-					.collect::<Vec<_>>()
-					.join(", ")
-			)
+		ValueType::Tensor { ty, .. } => format!("Tensor<{}>({})", display_element_type(*ty), display_dimensions(&value.tensor_shape().unwrap_or_default())),
+		ValueType::SparseTensor { ty, .. } => {
+			format!("SparseTensor<{}>({})", display_element_type(*ty), display_dimensions(&value.tensor_shape().unwrap_or_default()))
 		}
-		ValueType::Map { key, value } => format!("Map<{}, {}>", display_element_type(*key), display_element_type(*value)),
-		ValueType::Sequence(inner) => format!("Sequence<{}>", display_value_type(inner))
+		ValueType::Map { key, value } => format!("Map<{}, {}>", display_element_type(*key), display_value_type(value)),
+		ValueType::Sequence(inner) => format!("Sequence<{}>", display_value_type(inner)),
+		ValueType::Optional(inner) => format!("Optional<{}>", display_value_type(inner))
 	}
 }
 
@@ -0,0 +1,133 @@
+//! Derive macros for [`ort`](https://docs.rs/ort). These are re-exported by `ort` when its `macros` feature is enabled;
+//! use them through `ort` instead of depending on this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, LitStr, Type, parse_macro_input, spanned::Spanned};
+
+/// Implements `ort::Inputs` for a struct with named fields, mapping each field to the model input of the same name.
+///
+/// Each field is converted into an `ort::Value` with `Value::try_from`, and must implement `ort::TypedField` so its
+/// type can be checked against the model. Use `#[ort(name = "...")]` on a field to map it to an input with a different
+/// name.
+#[proc_macro_derive(Inputs, attributes(ort))]
+pub fn derive_inputs(input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+	expand_inputs(input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Implements `ort::Outputs` for a struct with named fields, mapping each field to the model output of the same name.
+///
+/// Each field is converted from an `ort::Value` with `TryFrom<Value>`, and must implement `ort::TypedField` so its
+/// type can be checked against the model. Use `#[ort(name = "...")]` on a field to map it to an output with a different
+/// name.
+#[proc_macro_derive(Outputs, attributes(ort))]
+pub fn derive_outputs(input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+	expand_outputs(input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+fn expand_inputs(input: DeriveInput) -> syn::Result<TokenStream2> {
+	let fields = named_fields(&input, "Inputs")?;
+	let ident = &input.ident;
+	let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+	let names: Vec<&LitStr> = fields.iter().map(|(_, name, _)| name).collect();
+	let field_types = field_types(&fields);
+	let inserts = fields.iter().map(|(field, name, _)| {
+		quote! {
+			inputs.insert(
+				::std::borrow::Cow::Borrowed(#name),
+				::std::convert::TryInto::<::ort::Value>::try_into(self.#field).map_err(::ort::Error::from)?
+			);
+		}
+	});
+
+	Ok(quote! {
+		impl #impl_generics ::ort::Inputs for #ident #ty_generics #where_clause {
+			const NAMES: &'static [&'static str] = &[#(#names),*];
+
+			#field_types
+
+			fn into_session_inputs(self) -> ::ort::Result<::ort::SessionInputs<'static>> {
+				let mut inputs = ::std::collections::HashMap::new();
+				#(#inserts)*
+				Ok(::ort::SessionInputs::ValueMap(inputs))
+			}
+		}
+	})
+}
+
+fn expand_outputs(input: DeriveInput) -> syn::Result<TokenStream2> {
+	let fields = named_fields(&input, "Outputs")?;
+	let ident = &input.ident;
+	let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+	let names: Vec<&LitStr> = fields.iter().map(|(_, name, _)| name).collect();
+	let field_types = field_types(&fields);
+	let extracts = fields.iter().map(|(field, name, _)| {
+		quote! {
+			#field: ::std::convert::TryFrom::<::ort::Value>::try_from(
+				outputs.remove(#name).ok_or_else(|| ::ort::Error::MissingOutput(::std::string::String::from(#name)))?
+			).map_err(::ort::Error::from)?
+		}
+	});
+
+	Ok(quote! {
+		impl #impl_generics ::ort::Outputs for #ident #ty_generics #where_clause {
+			const NAMES: &'static [&'static str] = &[#(#names),*];
+
+			#field_types
+
+			fn from_session_outputs(mut outputs: ::ort::SessionOutputs<'_>) -> ::ort::Result<Self> {
+				Ok(Self { #(#extracts),* })
+			}
+		}
+	})
+}
+
+/// Expands to a `field_types` implementation describing the type of each field via `ort::TypedField`.
+fn field_types(fields: &[(Ident, LitStr, Type)]) -> TokenStream2 {
+	let types = fields.iter().map(|(_, _, ty)| {
+		quote! {
+			(<#ty as ::ort::TypedField>::element_type(), <#ty as ::ort::TypedField>::rank())
+		}
+	});
+	quote! {
+		fn field_types() -> ::std::vec::Vec<::ort::FieldType> {
+			::std::vec![#(#types),*]
+		}
+	}
+}
+
+/// Returns each field of the struct alongside the name of the model input/output it maps to and its type.
+fn named_fields(input: &DeriveInput, derive: &str) -> syn::Result<Vec<(Ident, LitStr, Type)>> {
+	let fields = match &input.data {
+		Data::Struct(data) => match &data.fields {
+			Fields::Named(fields) => &fields.named,
+			_ => return Err(syn::Error::new(input.ident.span(), format!("`{derive}` can only be derived for structs with named fields"))),
+		},
+		_ => return Err(syn::Error::new(input.ident.span(), format!("`{derive}` can only be derived for structs"))),
+	};
+
+	fields
+		.iter()
+		.map(|field| {
+			let ident = field.ident.clone().expect("named fields have identifiers");
+			let mut name = LitStr::new(&ident.to_string(), ident.span());
+			for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("ort")) {
+				attr.parse_nested_meta(|meta| {
+					if meta.path.is_ident("name") {
+						name = meta.value()?.parse()?;
+						Ok(())
+					} else {
+						Err(meta.error("unknown `ort` attribute; expected `name`"))
+					}
+				})?;
+			}
+			if name.value().is_empty() {
+				return Err(syn::Error::new(field.span(), "input/output names cannot be empty"));
+			}
+			Ok((ident, name, field.ty.clone()))
+		})
+		.collect()
+}
@@ -796,9 +796,310 @@ fn bindgen_test_layout_OrtOpenVINOProviderOptions() {
 }
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
-pub struct OrtTrainingApi {
+pub struct OrtTrainingSession {
+	_unused: [u8; 0]
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct OrtCheckpointState {
 	_unused: [u8; 0]
 }
+#[repr(i32)]
+#[doc = " \\brief Type of property to be added to or returned from the ::OrtCheckpointState."]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum OrtPropertyType {
+	OrtIntProperty = 0,
+	OrtFloatProperty = 1,
+	OrtStringProperty = 2
+}
+#[doc = " \\brief The Training C API that holds onnxruntime training function pointers\n\n All the Training C API functions are defined inside this structure as pointers to functions.\n Call OrtApi::GetTrainingApi to get a pointer to this struct.\n\n \\nosubgrouping"]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct OrtTrainingApi {
+	pub LoadCheckpoint:
+		::std::option::Option<_system!(unsafe fn(checkpoint_path: *const ortchar, checkpoint_state: *mut *mut OrtCheckpointState) -> OrtStatusPtr)>,
+	pub SaveCheckpoint: ::std::option::Option<
+		_system!(unsafe fn(checkpoint_state: *mut OrtCheckpointState, checkpoint_path: *const ortchar, include_optimizer_state: bool) -> OrtStatusPtr)
+	>,
+	pub CreateTrainingSession: ::std::option::Option<
+		_system!(
+			unsafe fn(
+				env: *const OrtEnv,
+				options: *const OrtSessionOptions,
+				checkpoint_state: *mut OrtCheckpointState,
+				train_model_path: *const ortchar,
+				eval_model_path: *const ortchar,
+				optimizer_model_path: *const ortchar,
+				out: *mut *mut OrtTrainingSession
+			) -> OrtStatusPtr
+		)
+	>,
+	pub CreateTrainingSessionFromBuffer: ::std::option::Option<
+		_system!(
+			unsafe fn(
+				env: *const OrtEnv,
+				options: *const OrtSessionOptions,
+				checkpoint_state: *mut OrtCheckpointState,
+				train_model_data: *const ::std::os::raw::c_void,
+				train_data_length: size_t,
+				eval_model_data: *const ::std::os::raw::c_void,
+				eval_data_length: size_t,
+				optim_model_data: *const ::std::os::raw::c_void,
+				optim_data_length: size_t,
+				out: *mut *mut OrtTrainingSession
+			) -> OrtStatusPtr
+		)
+	>,
+	pub TrainingSessionGetTrainingModelOutputCount:
+		::std::option::Option<_system!(unsafe fn(sess: *const OrtTrainingSession, out: *mut size_t) -> OrtStatusPtr)>,
+	pub TrainingSessionGetEvalModelOutputCount: ::std::option::Option<_system!(unsafe fn(sess: *const OrtTrainingSession, out: *mut size_t) -> OrtStatusPtr)>,
+	pub TrainingSessionGetTrainingModelOutputName: ::std::option::Option<
+		_system!(
+			unsafe fn(sess: *const OrtTrainingSession, index: size_t, allocator: *mut OrtAllocator, output: *mut *mut ::std::os::raw::c_char) -> OrtStatusPtr
+		)
+	>,
+	pub TrainingSessionGetEvalModelOutputName: ::std::option::Option<
+		_system!(
+			unsafe fn(sess: *const OrtTrainingSession, index: size_t, allocator: *mut OrtAllocator, output: *mut *mut ::std::os::raw::c_char) -> OrtStatusPtr
+		)
+	>,
+	pub LazyResetGrad: ::std::option::Option<_system!(unsafe fn(session: *mut OrtTrainingSession) -> OrtStatusPtr)>,
+	pub TrainStep: ::std::option::Option<
+		_system!(
+			unsafe fn(
+				sess: *mut OrtTrainingSession,
+				run_options: *const OrtRunOptions,
+				inputs_len: size_t,
+				inputs: *const *const OrtValue,
+				outputs_len: size_t,
+				outputs: *mut *mut OrtValue
+			) -> OrtStatusPtr
+		)
+	>,
+	pub EvalStep: ::std::option::Option<
+		_system!(
+			unsafe fn(
+				sess: *const OrtTrainingSession,
+				run_options: *const OrtRunOptions,
+				inputs_len: size_t,
+				inputs: *const *const OrtValue,
+				outputs_len: size_t,
+				outputs: *mut *mut OrtValue
+			) -> OrtStatusPtr
+		)
+	>,
+	pub SetLearningRate: ::std::option::Option<_system!(unsafe fn(sess: *mut OrtTrainingSession, learning_rate: f32) -> OrtStatusPtr)>,
+	pub GetLearningRate: ::std::option::Option<_system!(unsafe fn(sess: *mut OrtTrainingSession, learning_rate: *mut f32) -> OrtStatusPtr)>,
+	pub OptimizerStep: ::std::option::Option<_system!(unsafe fn(sess: *mut OrtTrainingSession, run_options: *const OrtRunOptions) -> OrtStatusPtr)>,
+	pub RegisterLinearLRScheduler: ::std::option::Option<
+		_system!(unsafe fn(sess: *mut OrtTrainingSession, warmup_step_count: i64, total_step_count: i64, initial_lr: f32) -> OrtStatusPtr)
+	>,
+	pub SchedulerStep: ::std::option::Option<_system!(unsafe fn(sess: *mut OrtTrainingSession) -> OrtStatusPtr)>,
+	pub GetParametersSize: ::std::option::Option<_system!(unsafe fn(sess: *mut OrtTrainingSession, out: *mut size_t, trainable_only: bool) -> OrtStatusPtr)>,
+	pub CopyParametersToBuffer:
+		::std::option::Option<_system!(unsafe fn(sess: *mut OrtTrainingSession, parameters_buffer: *mut OrtValue, trainable_only: bool) -> OrtStatusPtr)>,
+	pub CopyBufferToParameters:
+		::std::option::Option<_system!(unsafe fn(sess: *mut OrtTrainingSession, parameters_buffer: *mut OrtValue, trainable_only: bool) -> OrtStatusPtr)>,
+	pub ReleaseTrainingSession: ::std::option::Option<_system!(unsafe fn(input: *mut OrtTrainingSession))>,
+	pub ReleaseCheckpointState: ::std::option::Option<_system!(unsafe fn(input: *mut OrtCheckpointState))>,
+	pub ExportModelForInferencing: ::std::option::Option<
+		_system!(
+			unsafe fn(
+				sess: *mut OrtTrainingSession,
+				inference_model_path: *const ortchar,
+				graph_outputs_len: size_t,
+				graph_output_names: *const *const ::std::os::raw::c_char
+			) -> OrtStatusPtr
+		)
+	>,
+	pub SetSeed: ::std::option::Option<_system!(unsafe fn(seed: i64) -> OrtStatusPtr)>,
+	pub TrainingSessionGetTrainingModelInputCount:
+		::std::option::Option<_system!(unsafe fn(sess: *const OrtTrainingSession, out: *mut size_t) -> OrtStatusPtr)>,
+	pub TrainingSessionGetEvalModelInputCount: ::std::option::Option<_system!(unsafe fn(sess: *const OrtTrainingSession, out: *mut size_t) -> OrtStatusPtr)>,
+	pub TrainingSessionGetTrainingModelInputName: ::std::option::Option<
+		_system!(
+			unsafe fn(sess: *const OrtTrainingSession, index: size_t, allocator: *mut OrtAllocator, output: *mut *mut ::std::os::raw::c_char) -> OrtStatusPtr
+		)
+	>,
+	pub TrainingSessionGetEvalModelInputName: ::std::option::Option<
+		_system!(
+			unsafe fn(sess: *const OrtTrainingSession, index: size_t, allocator: *mut OrtAllocator, output: *mut *mut ::std::os::raw::c_char) -> OrtStatusPtr
+		)
+	>,
+	pub AddProperty: ::std::option::Option<
+		_system!(
+			unsafe fn(
+				checkpoint_state: *mut OrtCheckpointState,
+				property_name: *const ::std::os::raw::c_char,
+				property_type: OrtPropertyType,
+				property_value: *mut ::std::os::raw::c_void
+			) -> OrtStatusPtr
+		)
+	>,
+	pub GetProperty: ::std::option::Option<
+		_system!(
+			unsafe fn(
+				checkpoint_state: *const OrtCheckpointState,
+				property_name: *const ::std::os::raw::c_char,
+				allocator: *mut OrtAllocator,
+				property_type: *mut OrtPropertyType,
+				property_value: *mut *mut ::std::os::raw::c_void
+			) -> OrtStatusPtr
+		)
+	>
+}
+#[test]
+fn bindgen_test_layout_OrtTrainingApi() {
+	const UNINIT: ::std::mem::MaybeUninit<OrtTrainingApi> = ::std::mem::MaybeUninit::uninit();
+	let ptr = UNINIT.as_ptr();
+	assert_eq!(::std::mem::size_of::<OrtTrainingApi>(), 232usize, concat!("Size of: ", stringify!(OrtTrainingApi)));
+	assert_eq!(::std::mem::align_of::<OrtTrainingApi>(), 8usize, concat!("Alignment of ", stringify!(OrtTrainingApi)));
+	assert_eq!(
+		unsafe { ::std::ptr::addr_of!((*ptr).LoadCheckpoint) as usize - ptr as usize },
+		0usize,
+		concat!("Offset of field: ", stringify!(OrtTrainingApi), "::", stringify!(LoadCheckpoint))
+	);
+	assert_eq!(
+		unsafe { ::std::ptr::addr_of!((*ptr).SaveCheckpoint) as usize - ptr as usize },
+		8usize,
+		concat!("Offset of field: ", stringify!(OrtTrainingApi), "::", stringify!(SaveCheckpoint))
+	);
+	assert_eq!(
+		unsafe { ::std::ptr::addr_of!((*ptr).CreateTrainingSession) as usize - ptr as usize },
+		16usize,
+		concat!("Offset of field: ", stringify!(OrtTrainingApi), "::", stringify!(CreateTrainingSession))
+	);
+	assert_eq!(
+		unsafe { ::std::ptr::addr_of!((*ptr).CreateTrainingSessionFromBuffer) as usize - ptr as usize },
+		24usize,
+		concat!("Offset of field: ", stringify!(OrtTrainingApi), "::", stringify!(CreateTrainingSessionFromBuffer))
+	);
+	assert_eq!(
+		unsafe { ::std::ptr::addr_of!((*ptr).TrainingSessionGetTrainingModelOutputCount) as usize - ptr as usize },
+		32usize,
+		concat!("Offset of field: ", stringify!(OrtTrainingApi), "::", stringify!(TrainingSessionGetTrainingModelOutputCount))
+	);
+	assert_eq!(
+		unsafe { ::std::ptr::addr_of!((*ptr).TrainingSessionGetEvalModelOutputCount) as usize - ptr as usize },
+		40usize,
+		concat!("Offset of field: ", stringify!(OrtTrainingApi), "::", stringify!(TrainingSessionGetEvalModelOutputCount))
+	);
+	assert_eq!(
+		unsafe { ::std::ptr::addr_of!((*ptr).TrainingSessionGetTrainingModelOutputName) as usize - ptr as usize },
+		48usize,
+		concat!("Offset of field: ", stringify!(OrtTrainingApi), "::", stringify!(TrainingSessionGetTrainingModelOutputName))
+	);
+	assert_eq!(
+		unsafe { ::std::ptr::addr_of!((*ptr).TrainingSessionGetEvalModelOutputName) as usize - ptr as usize },
+		56usize,
+		concat!("Offset of field: ", stringify!(OrtTrainingApi), "::", stringify!(TrainingSessionGetEvalModelOutputName))
+	);
+	assert_eq!(
+		unsafe { ::std::ptr::addr_of!((*ptr).LazyResetGrad) as usize - ptr as usize },
+		64usize,
+		concat!("Offset of field: ", stringify!(OrtTrainingApi), "::", stringify!(LazyResetGrad))
+	);
+	assert_eq!(
+		unsafe { ::std::ptr::addr_of!((*ptr).TrainStep) as usize - ptr as usize },
+		72usize,
+		concat!("Offset of field: ", stringify!(OrtTrainingApi), "::", stringify!(TrainStep))
+	);
+	assert_eq!(
+		unsafe { ::std::ptr::addr_of!((*ptr).EvalStep) as usize - ptr as usize },
+		80usize,
+		concat!("Offset of field: ", stringify!(OrtTrainingApi), "::", stringify!(EvalStep))
+	);
+	assert_eq!(
+		unsafe { ::std::ptr::addr_of!((*ptr).SetLearningRate) as usize - ptr as usize },
+		88usize,
+		concat!("Offset of field: ", stringify!(OrtTrainingApi), "::", stringify!(SetLearningRate))
+	);
+	assert_eq!(
+		unsafe { ::std::ptr::addr_of!((*ptr).GetLearningRate) as usize - ptr as usize },
+		96usize,
+		concat!("Offset of field: ", stringify!(OrtTrainingApi), "::", stringify!(GetLearningRate))
+	);
+	assert_eq!(
+		unsafe { ::std::ptr::addr_of!((*ptr).OptimizerStep) as usize - ptr as usize },
+		104usize,
+		concat!("Offset of field: ", stringify!(OrtTrainingApi), "::", stringify!(OptimizerStep))
+	);
+	assert_eq!(
+		unsafe { ::std::ptr::addr_of!((*ptr).RegisterLinearLRScheduler) as usize - ptr as usize },
+		112usize,
+		concat!("Offset of field: ", stringify!(OrtTrainingApi), "::", stringify!(RegisterLinearLRScheduler))
+	);
+	assert_eq!(
+		unsafe { ::std::ptr::addr_of!((*ptr).SchedulerStep) as usize - ptr as usize },
+		120usize,
+		concat!("Offset of field: ", stringify!(OrtTrainingApi), "::", stringify!(SchedulerStep))
+	);
+	assert_eq!(
+		unsafe { ::std::ptr::addr_of!((*ptr).GetParametersSize) as usize - ptr as usize },
+		128usize,
+		concat!("Offset of field: ", stringify!(OrtTrainingApi), "::", stringify!(GetParametersSize))
+	);
+	assert_eq!(
+		unsafe { ::std::ptr::addr_of!((*ptr).CopyParametersToBuffer) as usize - ptr as usize },
+		136usize,
+		concat!("Offset of field: ", stringify!(OrtTrainingApi), "::", stringify!(CopyParametersToBuffer))
+	);
+	assert_eq!(
+		unsafe { ::std::ptr::addr_of!((*ptr).CopyBufferToParameters) as usize - ptr as usize },
+		144usize,
+		concat!("Offset of field: ", stringify!(OrtTrainingApi), "::", stringify!(CopyBufferToParameters))
+	);
+	assert_eq!(
+		unsafe { ::std::ptr::addr_of!((*ptr).ReleaseTrainingSession) as usize - ptr as usize },
+		152usize,
+		concat!("Offset of field: ", stringify!(OrtTrainingApi), "::", stringify!(ReleaseTrainingSession))
+	);
+	assert_eq!(
+		unsafe { ::std::ptr::addr_of!((*ptr).ReleaseCheckpointState) as usize - ptr as usize },
+		160usize,
+		concat!("Offset of field: ", stringify!(OrtTrainingApi), "::", stringify!(ReleaseCheckpointState))
+	);
+	assert_eq!(
+		unsafe { ::std::ptr::addr_of!((*ptr).ExportModelForInferencing) as usize - ptr as usize },
+		168usize,
+		concat!("Offset of field: ", stringify!(OrtTrainingApi), "::", stringify!(ExportModelForInferencing))
+	);
+	assert_eq!(
+		unsafe { ::std::ptr::addr_of!((*ptr).SetSeed) as usize - ptr as usize },
+		176usize,
+		concat!("Offset of field: ", stringify!(OrtTrainingApi), "::", stringify!(SetSeed))
+	);
+	assert_eq!(
+		unsafe { ::std::ptr::addr_of!((*ptr).TrainingSessionGetTrainingModelInputCount) as usize - ptr as usize },
+		184usize,
+		concat!("Offset of field: ", stringify!(OrtTrainingApi), "::", stringify!(TrainingSessionGetTrainingModelInputCount))
+	);
+	assert_eq!(
+		unsafe { ::std::ptr::addr_of!((*ptr).TrainingSessionGetEvalModelInputCount) as usize - ptr as usize },
+		192usize,
+		concat!("Offset of field: ", stringify!(OrtTrainingApi), "::", stringify!(TrainingSessionGetEvalModelInputCount))
+	);
+	assert_eq!(
+		unsafe { ::std::ptr::addr_of!((*ptr).TrainingSessionGetTrainingModelInputName) as usize - ptr as usize },
+		200usize,
+		concat!("Offset of field: ", stringify!(OrtTrainingApi), "::", stringify!(TrainingSessionGetTrainingModelInputName))
+	);
+	assert_eq!(
+		unsafe { ::std::ptr::addr_of!((*ptr).TrainingSessionGetEvalModelInputName) as usize - ptr as usize },
+		208usize,
+		concat!("Offset of field: ", stringify!(OrtTrainingApi), "::", stringify!(TrainingSessionGetEvalModelInputName))
+	);
+	assert_eq!(
+		unsafe { ::std::ptr::addr_of!((*ptr).AddProperty) as usize - ptr as usize },
+		216usize,
+		concat!("Offset of field: ", stringify!(OrtTrainingApi), "::", stringify!(AddProperty))
+	);
+	assert_eq!(
+		unsafe { ::std::ptr::addr_of!((*ptr).GetProperty) as usize - ptr as usize },
+		224usize,
+		concat!("Offset of field: ", stringify!(OrtTrainingApi), "::", stringify!(GetProperty))
+	);
+}
 #[doc = " \\brief The helper interface to get the right version of OrtApi\n\n Get a pointer to this structure through ::OrtGetApiBase"]
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
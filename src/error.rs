@@ -4,7 +4,7 @@ use std::{convert::Infallible, io, path::PathBuf, string};
 
 use thiserror::Error;
 
-use super::{char_p_to_string, ortsys, tensor::TensorElementDataType};
+use super::{char_p_to_string, ortsys, tensor::TensorElementDataType, ValueType};
 
 /// Type alias for the Result type returned by ORT functions.
 pub type Result<T, E = Error> = std::result::Result<T, E>;
@@ -30,6 +30,123 @@ pub enum Error {
 	/// Error occurred when creating an ONNX session.
 	#[error("Failed to create ONNX Runtime session: {0}")]
 	CreateSession(ErrorInternal),
+	/// Error occurred when creating a [`crate::PrepackedWeights`] container.
+	#[error("Failed to create prepacked weights container: {0}")]
+	CreatePrepackedWeights(ErrorInternal),
+	/// Error occurred when adding an initializer to session options.
+	#[error("Failed to add initializer: {0}")]
+	AddInitializer(ErrorInternal),
+	/// The number of initializer names passed to [`crate::SessionBuilder::with_external_initializers`] does not match
+	/// the number of values.
+	#[error("Expected {names} initializer values, but got {values}")]
+	InitializerCountMismatch {
+		/// Number of initializer names
+		names: usize,
+		/// Number of initializer values
+		values: usize
+	},
+	/// Attempted to memory-map a model which is not in the `.ort` format.
+	#[error("Model is not in the `.ort` format, so it cannot be loaded without copying")]
+	NotOrtFormat,
+	/// Failed to memory-map a model file.
+	#[error("Failed to memory-map model file: {0}")]
+	MapModelFile(io::Error),
+	/// The model could not be parsed to locate its external data.
+	#[error("Failed to parse model: malformed protobuf")]
+	MalformedModel,
+	/// No buffer was provided for an external data file referenced by the model.
+	#[error("No buffer was provided for external data file `{0}`")]
+	MissingExternalData(String),
+	/// An initializer stored as external data has an element type which cannot be loaded from a buffer.
+	#[error("Initializer `{name}` has unsupported external data type {data_type}")]
+	UnsupportedExternalDataType {
+		/// Name of the initializer
+		name: String,
+		/// The `TensorProto.DataType` of the initializer
+		data_type: i32
+	},
+	/// The length of an initializer's external data does not match the size of the initializer.
+	#[error("External data of initializer `{name}` should be {expected} bytes, but is {actual} bytes")]
+	ExternalDataLengthMismatch {
+		/// Name of the initializer
+		name: String,
+		/// Size of the initializer in bytes
+		expected: usize,
+		/// Length of the external data in bytes
+		actual: usize
+	},
+	/// The external data of an initializer lies outside of its buffer.
+	#[error("External data of initializer `{0}` is out of bounds of its buffer")]
+	ExternalDataOutOfBounds(String),
+	/// Error occurred when creating an [`crate::OperatorDomain`].
+	#[error("Failed to create custom operator domain: {0}")]
+	CreateOperatorDomain(ErrorInternal),
+	/// Error occurred when adding a custom operator to an [`crate::OperatorDomain`].
+	#[error("Failed to add custom operator: {0}")]
+	AddOperator(ErrorInternal),
+	/// Error occurred when registering an [`crate::OperatorDomain`] with session options.
+	#[error("Failed to add custom operator domain: {0}")]
+	AddOperatorDomain(ErrorInternal),
+	/// Error occurred when reading an attribute of a node using a custom operator.
+	#[error("Failed to get kernel attribute: {0}")]
+	GetKernelAttribute(ErrorInternal),
+	/// Error occurred when getting an input of a custom operator.
+	#[error("Failed to get kernel input: {0}")]
+	GetKernelInput(ErrorInternal),
+	/// Error occurred when allocating an output of a custom operator.
+	#[error("Failed to get kernel output: {0}")]
+	GetKernelOutput(ErrorInternal),
+	/// An output of a custom operator was allocated more than once in the same computation.
+	#[error("Output {0} of custom operator was already allocated")]
+	KernelOutputAlreadyAllocated(usize),
+	/// String tensors cannot be viewed as arrays in a custom operator.
+	#[error("String tensors cannot be accessed as arrays in custom operators")]
+	KernelStringTensor,
+	/// An error returned by the implementation of a [`crate::CustomOp`].
+	#[error("{0}")]
+	CustomError(#[from] Box<dyn std::error::Error + Send + Sync>),
+	/// The loaded ONNX Runtime binaries were not built with training support.
+	#[error("ONNX Runtime was not built with training support")]
+	TrainingNotEnabled,
+	/// Error occurred when loading a [`crate::Checkpoint`].
+	#[error("Failed to load checkpoint: {0}")]
+	LoadCheckpoint(ErrorInternal),
+	/// Error occurred when saving a [`crate::Checkpoint`].
+	#[error("Failed to save checkpoint: {0}")]
+	SaveCheckpoint(ErrorInternal),
+	/// Error occurred when creating a [`crate::Trainer`].
+	#[error("Failed to create training session: {0}")]
+	CreateTrainingSession(ErrorInternal),
+	/// Error occurred when running a training step.
+	#[error("Failed to run training step: {0}")]
+	TrainStep(ErrorInternal),
+	/// Error occurred when running an evaluation step.
+	#[error("Failed to run evaluation step: {0}")]
+	EvalStep(ErrorInternal),
+	/// Error occurred when running an optimizer step.
+	#[error("Failed to run optimizer step: {0}")]
+	OptimizerStep(ErrorInternal),
+	/// Error occurred when resetting the gradients of a [`crate::Trainer`].
+	#[error("Failed to reset gradients: {0}")]
+	LazyResetGrad(ErrorInternal),
+	/// Error occurred when setting the learning rate of a [`crate::Trainer`].
+	#[error("Failed to set learning rate: {0}")]
+	SetLearningRate(ErrorInternal),
+	/// Error occurred when getting the learning rate of a [`crate::Trainer`].
+	#[error("Failed to get learning rate: {0}")]
+	GetLearningRate(ErrorInternal),
+	/// Error occurred when registering a learning rate scheduler with a [`crate::Trainer`].
+	#[error("Failed to register learning rate scheduler: {0}")]
+	RegisterLearningRateScheduler(ErrorInternal),
+	/// Error occurred when stepping the learning rate scheduler of a [`crate::Trainer`].
+	#[error("Failed to step learning rate scheduler: {0}")]
+	SchedulerStep(ErrorInternal),
+	/// Error occurred when exporting a trained model for inference.
+	#[error("Failed to export model for inference: {0}")]
+	ExportModelForInference(ErrorInternal),
+	/// Error occurred when reading a session configuration entry.
+	#[error("Failed to get session config entry: {0}")]
+	GetSessionConfigEntry(ErrorInternal),
 	/// Error occurred when creating an IO binding.
 	#[error("Failed to create IO binding: {0}")]
 	CreateIoBinding(ErrorInternal),
@@ -63,6 +180,90 @@ pub enum Error {
 	/// Error occurred when getting sequence element type
 	#[error("Failed to get sequence element type: {0}")]
 	GetSequenceElementType(ErrorInternal),
+	/// Error occurred when casting ONNX type information to optional type info
+	#[error("Failed to cast type info to optional type info: {0}")]
+	CastTypeInfoToOptionalTypeInfo(ErrorInternal),
+	/// Error occurred when getting the type contained in an optional type
+	#[error("Failed to get optional contained type: {0}")]
+	GetOptionalContainedTypeInfo(ErrorInternal),
+	/// Error occurred when creating a sequence or map value
+	#[error("Failed to create value: {0}")]
+	CreateValue(ErrorInternal),
+	/// Error occurred when getting the ONNX type of a value
+	#[error("Failed to get value type: {0}")]
+	GetValueType(ErrorInternal),
+	/// Error occurred when getting the number of elements in a sequence or map value
+	#[error("Failed to get value count: {0}")]
+	GetValueCount(ErrorInternal),
+	/// Error occurred when getting an element of a sequence or map value
+	#[error("Failed to get value: {0}")]
+	GetValue(ErrorInternal),
+	/// A value was not of the ONNX type required by the operation, e.g. extracting a sequence from a tensor
+	#[error("Expected value of type {expected:?}, got {actual:?}")]
+	ValueTypeMismatch {
+		/// The ONNX type required by the operation
+		expected: ort_sys::ONNXType,
+		/// The actual ONNX type of the value
+		actual: ort_sys::ONNXType
+	},
+	/// Error occurred when creating a sparse tensor
+	#[error("Failed to create sparse tensor: {0}")]
+	CreateSparseTensor(ErrorInternal),
+	/// Error occurred when filling a sparse tensor with values & indices
+	#[error("Failed to fill sparse tensor: {0}")]
+	FillSparseTensor(ErrorInternal),
+	/// Error occurred when checking if a value is a sparse tensor
+	#[error("Failed to check if value is a sparse tensor: {0}")]
+	IsSparseTensor(ErrorInternal),
+	/// Error occurred when getting the format of a sparse tensor
+	#[error("Failed to get sparse tensor format: {0}")]
+	GetSparseTensorFormat(ErrorInternal),
+	/// Error occurred when getting the values of a sparse tensor
+	#[error("Failed to get sparse tensor values: {0}")]
+	GetSparseTensorValues(ErrorInternal),
+	/// Error occurred when getting the indices of a sparse tensor
+	#[error("Failed to get sparse tensor indices: {0}")]
+	GetSparseTensorIndices(ErrorInternal),
+	/// The format of a sparse tensor was undefined.
+	#[error("Undefined sparse tensor format")]
+	UndefinedSparseFormat,
+	/// The shape of a sparse tensor's values or indices does not match the number of elements provided.
+	#[error("Sparse tensor shape {shape:?} does not match the {len} elements provided")]
+	SparseTensorShapeMismatch {
+		/// The shape of the values or indices
+		shape: Vec<i64>,
+		/// The number of values or indices provided
+		len: usize
+	},
+	/// The number of a COO or CSR sparse tensor's indices does not match its values or dense shape.
+	#[error("Expected {expected} sparse tensor indices, got {len}")]
+	SparseIndicesLengthMismatch {
+		/// The expected number of indices
+		expected: usize,
+		/// The number of indices provided
+		len: usize
+	},
+	/// An index of a COO or CSR sparse tensor lies outside of its dense shape.
+	#[error("Sparse tensor index {index} is out of bounds for a dimension of size {bound}")]
+	SparseIndexOutOfBounds {
+		/// The out-of-bounds index
+		index: i64,
+		/// The size of the dimension the index refers to
+		bound: i64
+	},
+	/// The dense shape of a sparse tensor is invalid for its format, i.e. has negative dimensions, or is not
+	/// 2-dimensional for CSR tensors.
+	#[error("Invalid dense shape {0:?} for sparse tensor")]
+	InvalidSparseDenseShape(Vec<i64>),
+	/// The outer indices of a CSR sparse tensor do not start at 0, decrease, or do not end at the number of values.
+	#[error("Invalid CSR outer indices {0:?}")]
+	InvalidCsrOuterIndices(Vec<i64>),
+	/// String sparse tensors cannot be extracted, as their values are not stored contiguously.
+	#[error("String sparse tensors cannot be extracted")]
+	SparseStringTensor,
+	/// The model uses an ONNX type which is not supported by `ort`
+	#[error("Unsupported ONNX type: {0:?}")]
+	UnsupportedOnnxType(ort_sys::ONNXType),
 	/// Error occurred when getting tensor elements type
 	#[error("Failed to get tensor element type: {0}")]
 	GetTensorElementType(ErrorInternal),
@@ -72,6 +273,9 @@ pub enum Error {
 	/// Error occurred when getting ONNX dimensions
 	#[error("Failed to get dimensions: {0}")]
 	GetDimensions(ErrorInternal),
+	/// Error occurred when getting the symbolic names of ONNX dimensions
+	#[error("Failed to get symbolic dimensions: {0}")]
+	GetSymbolicDimensions(ErrorInternal),
 	/// Error occurred when getting string length
 	#[error("Failed to get string tensor length: {0}")]
 	GetStringTensorDataLength(ErrorInternal),
@@ -96,6 +300,34 @@ pub enum Error {
 	/// Error occurred when ONNX inference operation was called
 	#[error("Failed to run inference on model: {0}")]
 	SessionRun(ErrorInternal),
+	/// Error occurred when creating run options.
+	#[error("Failed to create run options: {0}")]
+	CreateRunOptions(ErrorInternal),
+	/// Error occurred when setting the tag of run options.
+	#[error("Failed to set run tag: {0}")]
+	RunOptionsSetTag(ErrorInternal),
+	/// Error occurred when getting the tag of run options.
+	#[error("Failed to get run tag: {0}")]
+	RunOptionsGetTag(ErrorInternal),
+	/// Error occurred when setting the log severity level of run options.
+	#[error("Failed to set run log severity level: {0}")]
+	RunOptionsSetLogLevel(ErrorInternal),
+	/// Error occurred when adding a config entry to run options.
+	#[error("Failed to add run config entry: {0}")]
+	RunOptionsAddConfigEntry(ErrorInternal),
+	/// Error occurred when setting the terminate flag of run options.
+	#[error("Failed to terminate run: {0}")]
+	RunOptionsSetTerminate(ErrorInternal),
+	/// Error occurred when clearing the terminate flag of run options.
+	#[error("Failed to unset run termination flag: {0}")]
+	RunOptionsUnsetTerminate(ErrorInternal),
+	/// The run was terminated via [`RunOptions::terminate`](crate::RunOptions::terminate) or
+	/// [`RunHandle::terminate`](crate::RunHandle::terminate) before it could complete.
+	#[error("Run was terminated")]
+	RunTerminated,
+	/// An output requested via [`OutputSelector`](crate::OutputSelector) does not exist in the session.
+	#[error("Session has no output named `{0}`")]
+	UnknownOutput(String),
 	/// Error occurred when ONNX inference operation was called using `IoBinding`.
 	#[error("Failed to run inference on model with IoBinding: {0}")]
 	SessionRunWithIoBinding(ErrorInternal),
@@ -117,6 +349,79 @@ pub enum Error {
 	/// Dimensions of input data and the ONNX model do not match.
 	#[error("Dimensions do not match: {0:?}")]
 	NonMatchingDimensions(NonMatchingDimensionsError),
+	/// The number of inputs provided to a run does not match the number of inputs of the model.
+	#[error("Expected {expected} inputs, but got {actual}")]
+	InputCountMismatch {
+		/// Number of inputs expected by the model
+		expected: usize,
+		/// Number of inputs provided
+		actual: usize
+	},
+	/// An output expected to be returned by a run was not present.
+	#[error("Missing output `{0}`")]
+	MissingOutput(String),
+	/// An input required by the model was not provided.
+	#[error("Missing input `{0}`")]
+	MissingInput(String),
+	/// An input was provided which does not exist in the model.
+	#[error("Model has no input named `{0}`")]
+	UnknownInput(String),
+	/// A non-tensor value was provided for a tensor input.
+	#[error("Expected input `{0}` to be a tensor")]
+	InputNotTensor(String),
+	/// The element type of an input tensor does not match the type expected by the model.
+	#[error("Data type mismatch for input `{name}`: expected {expected:?}, got {actual:?}")]
+	InputDataTypeMismatch {
+		/// Name of the input
+		name: String,
+		/// Element type expected by the model
+		expected: TensorElementDataType,
+		/// Element type of the provided tensor
+		actual: TensorElementDataType
+	},
+	/// The type of a non-tensor input, such as the element type of a sequence or map, does not match the type expected
+	/// by the model.
+	#[error("Type mismatch for input `{name}`: expected {expected:?}, got {actual:?}")]
+	InputTypeMismatch {
+		/// Name of the input
+		name: String,
+		/// Type expected by the model
+		expected: Box<ValueType>,
+		/// Type of the provided value
+		actual: Box<ValueType>
+	},
+	/// The shape of an input tensor does not match the shape expected by the model.
+	#[error("Shape mismatch for input `{name}`: expected {expected:?}, got {actual:?}")]
+	InputShapeMismatch {
+		/// Name of the input
+		name: String,
+		/// Dimensions expected by the model; negative dimensions are dynamic
+		expected: Vec<i64>,
+		/// Dimensions of the provided tensor
+		actual: Vec<i64>
+	},
+	/// A field of a [`TypedSession`](crate::TypedSession)'s inputs or outputs expects a tensor with a different element
+	/// type than the model's input or output of the same name.
+	#[error("`{name}` is {actual:?} in the model, but expected a tensor of {expected:?}")]
+	TypedDataTypeMismatch {
+		/// Name of the input or output
+		name: String,
+		/// Element type expected by the field
+		expected: TensorElementDataType,
+		/// Type of the input or output in the model
+		actual: ValueType
+	},
+	/// A field of a [`TypedSession`](crate::TypedSession)'s inputs or outputs expects a tensor with a different number
+	/// of dimensions than the model's input or output of the same name.
+	#[error("`{name}` has {actual} dimensions in the model, but expected {expected}")]
+	TypedRankMismatch {
+		/// Name of the input or output
+		name: String,
+		/// Number of dimensions expected by the field
+		expected: usize,
+		/// Number of dimensions of the input or output in the model
+		actual: usize
+	},
 	/// File does not exist
 	#[error("File `{filename:?}` does not exist")]
 	FileDoesNotExist {
@@ -181,7 +486,32 @@ pub enum Error {
 	#[error("Failed to clear IO binding: {0}")]
 	ClearBinding(ErrorInternal),
 	#[error("Error when retrieving session outputs from `IoBinding`: {0}")]
-	GetBoundOutputs(ErrorInternal)
+	GetBoundOutputs(ErrorInternal),
+	/// An input or output cannot be batched because it has no dynamic dimension or is not a fixed-size tensor.
+	#[error("Input/output `{0}` cannot be batched")]
+	NotBatchable(String),
+	/// The inputs of a batch request do not agree on the number of samples along the batch axis.
+	#[error("Input `{name}` has a batch size of {actual}, but other inputs have a batch size of {expected}")]
+	InconsistentBatchSize {
+		/// Name of the input
+		name: String,
+		/// Batch size of the other inputs
+		expected: usize,
+		/// Batch size of this input
+		actual: usize
+	},
+	/// A batched output could not be split back into per-request outputs.
+	#[error("Output `{0}` does not match the batch size of its inputs")]
+	BatchOutputMismatch(String),
+	/// Failed to spawn the worker thread of a [`crate::BatchRunner`].
+	#[error("Failed to spawn batch runner thread: {0}")]
+	SpawnBatchRunner(io::Error),
+	/// The worker thread of a [`crate::BatchRunner`] has stopped.
+	#[error("Batch runner has stopped")]
+	BatchRunnerStopped,
+	/// Attempted to create a [`crate::SessionPool`] with no sessions.
+	#[error("Session pool must contain at least one session")]
+	EmptySessionPool
 }
 
 impl From<Infallible> for Error {
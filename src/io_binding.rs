@@ -1,6 +1,12 @@
 use std::{ffi::CString, fmt::Debug, ptr, sync::Arc};
 
-use crate::{memory::MemoryInfo, ortsys, session::output::SessionOutputs, value::Value, Error, Result, Session};
+use crate::{
+	memory::MemoryInfo,
+	ortsys,
+	session::{output::SessionOutputs, run_options::map_run_error},
+	value::Value,
+	Error, Result, RunOptions, Session
+};
 
 /// Enables binding of session inputs and/or outputs to pre-allocated memory.
 ///
@@ -58,9 +64,19 @@ impl<'s> IoBinding<'s> {
 		Ok(())
 	}
 
+	/// Performs inference on the session using the bound inputs and outputs.
 	pub fn run<'i: 's>(&'i self) -> Result<SessionOutputs<'s>> {
-		let run_options_ptr: *const ort_sys::OrtRunOptions = std::ptr::null();
-		ortsys![unsafe RunWithBinding(self.session.inner.session_ptr, run_options_ptr, self.ptr) -> Error::SessionRunWithIoBinding];
+		self.run_inner(None)
+	}
+
+	/// Performs inference on the session using the bound inputs and outputs, with the given [`RunOptions`].
+	pub fn run_with_options<'i: 's>(&'i self, run_options: &RunOptions) -> Result<SessionOutputs<'s>> {
+		self.run_inner(Some(run_options))
+	}
+
+	fn run_inner<'i: 's>(&'i self, run_options: Option<&RunOptions>) -> Result<SessionOutputs<'s>> {
+		let run_options_ptr = run_options.map(|o| o.ptr() as *const _).unwrap_or_else(std::ptr::null);
+		ortsys![unsafe RunWithBinding(self.session.inner.session_ptr, run_options_ptr, self.ptr) -> map_run_error(run_options, Error::SessionRunWithIoBinding)];
 
 		let mut count = self.output_names.len() as ort_sys::size_t;
 		if count > 0 {
@@ -17,8 +17,12 @@ pub(crate) mod execution_providers;
 pub(crate) mod io_binding;
 pub(crate) mod memory;
 pub(crate) mod metadata;
+#[cfg(feature = "custom-ops")]
+pub(crate) mod operator;
 pub(crate) mod session;
 pub(crate) mod tensor;
+#[cfg(feature = "training")]
+pub(crate) mod training;
 pub(crate) mod value;
 
 #[cfg(feature = "load-dynamic")]
@@ -30,6 +34,9 @@ use std::{
 	sync::{atomic::AtomicPtr, Arc, Mutex, OnceLock}
 };
 
+#[cfg(feature = "macros")]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+pub use ort_macros::{Inputs, Outputs};
 use tracing::Level;
 
 #[cfg(feature = "load-dynamic")]
@@ -43,12 +50,24 @@ pub use self::execution_providers::*;
 pub use self::io_binding::IoBinding;
 pub use self::memory::{AllocationDevice, Allocator, MemoryInfo};
 pub use self::metadata::ModelMetadata;
-pub use self::session::{InMemorySession, Session, SessionBuilder, SessionInputs, SessionOutputs, SharedSessionInner};
+#[cfg(feature = "custom-ops")]
+#[cfg_attr(docsrs, doc(cfg(feature = "custom-ops")))]
+pub use self::operator::{
+	CustomOp, InputOutputCharacteristic, KernelAttribute, KernelAttributes, KernelContext, OperatorDomain, OperatorInput, OperatorOutput
+};
+pub use self::session::{
+	optimize_model, BatchRunner, BatchRunnerBuilder, FieldType, InMemorySession, InferenceFut, Inputs, ModelFormat, OutputSelector, Outputs, PooledSession,
+	PrepackedWeights, RunHandle, RunOptions, Session, SessionBuilder, SessionConfig, SessionInputs, SessionOutputs, SessionPool, SharedSessionInner,
+	TypedField, TypedSession
+};
 #[cfg(feature = "ndarray")]
 #[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
 pub use self::tensor::{ArrayExtensions, ArrayViewHolder, Tensor, TensorData};
-pub use self::tensor::{ExtractTensorData, IntoTensorElementDataType, TensorElementDataType};
-pub use self::value::{Value, ValueType};
+pub use self::tensor::{ExtractTensorData, IntoTensorElementDataType, SparseFormat, SparseIndices, SparseTensor, TensorElementDataType};
+#[cfg(feature = "training")]
+#[cfg_attr(docsrs, doc(cfg(feature = "training")))]
+pub use self::training::{Checkpoint, Trainer};
+pub use self::value::{Dim, MapElement, MapKey, MapValue, Value, ValueType};
 
 #[cfg(not(all(target_arch = "x86", target_os = "windows")))]
 macro_rules! extern_system_fn {
@@ -420,6 +439,33 @@ impl From<MemType> for ort_sys::OrtMemType {
 	}
 }
 
+/// Severity level of ONNX Runtime log messages.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LogLevel {
+	/// Verbose informational messages (least severe).
+	Verbose,
+	/// Informational messages.
+	Info,
+	/// Warning messages.
+	Warning,
+	/// Error messages.
+	Error,
+	/// Fatal error messages (most severe).
+	Fatal
+}
+
+impl From<LogLevel> for ort_sys::OrtLoggingLevel {
+	fn from(val: LogLevel) -> Self {
+		match val {
+			LogLevel::Verbose => ort_sys::OrtLoggingLevel::ORT_LOGGING_LEVEL_VERBOSE,
+			LogLevel::Info => ort_sys::OrtLoggingLevel::ORT_LOGGING_LEVEL_INFO,
+			LogLevel::Warning => ort_sys::OrtLoggingLevel::ORT_LOGGING_LEVEL_WARNING,
+			LogLevel::Error => ort_sys::OrtLoggingLevel::ORT_LOGGING_LEVEL_ERROR,
+			LogLevel::Fatal => ort_sys::OrtLoggingLevel::ORT_LOGGING_LEVEL_FATAL
+		}
+	}
+}
+
 #[cfg(test)]
 mod test {
 	use super::*;
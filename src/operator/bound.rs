@@ -0,0 +1,248 @@
+use std::{
+	ffi::CString,
+	os::raw::{c_char, c_int, c_void},
+	panic::{self, AssertUnwindSafe},
+	ptr
+};
+
+use super::{
+	io::{OperatorInput, OperatorOutput},
+	kernel::{KernelAttributes, KernelContext},
+	CustomOp
+};
+use crate::{extern_system_fn, ortsys, Result};
+
+/// Adapts a [`CustomOp`] to an [`ort_sys::OrtCustomOp`]. ONNX Runtime only ever sees a pointer to `implementation`,
+/// which, being the first field of this `#[repr(C)]` struct, can be cast back to the whole [`BoundOperator`] in
+/// callbacks.
+#[repr(C)]
+pub(crate) struct BoundOperator {
+	implementation: ort_sys::OrtCustomOp,
+	name: CString,
+	inputs: Vec<OperatorInput>,
+	outputs: Vec<OperatorOutput>,
+	operator: Box<dyn CustomOp>
+}
+
+impl BoundOperator {
+	pub(crate) fn new<O: CustomOp>(operator: O) -> Result<Box<Self>> {
+		Ok(Box::new(Self {
+			implementation: ort_sys::OrtCustomOp {
+				version: ort_sys::ORT_API_VERSION,
+				// superseded by the fallible `CreateKernelV2` & `KernelComputeV2`
+				CreateKernel: None,
+				KernelCompute: None,
+				GetName: Some(get_name),
+				GetExecutionProviderType: Some(get_execution_provider_type),
+				GetInputType: Some(get_input_type),
+				GetInputTypeCount: Some(get_input_type_count),
+				GetOutputType: Some(get_output_type),
+				GetOutputTypeCount: Some(get_output_type_count),
+				KernelDestroy: Some(kernel_destroy),
+				GetInputCharacteristic: Some(get_input_characteristic),
+				GetOutputCharacteristic: Some(get_output_characteristic),
+				GetInputMemoryType: Some(get_input_memory_type),
+				GetVariadicInputMinArity: Some(get_variadic_min_arity),
+				GetVariadicInputHomogeneity: Some(get_variadic_homogeneity),
+				GetVariadicOutputMinArity: Some(get_variadic_min_arity),
+				GetVariadicOutputHomogeneity: Some(get_variadic_homogeneity),
+				CreateKernelV2: Some(create_kernel),
+				KernelComputeV2: Some(kernel_compute)
+			},
+			name: CString::new(operator.name())?,
+			inputs: operator.inputs(),
+			outputs: operator.outputs(),
+			operator: Box::new(operator)
+		}))
+	}
+
+	pub(crate) fn name(&self) -> &str {
+		self.operator.name()
+	}
+
+	/// Returns the pointer to pass to `CustomOpDomain_Add`.
+	pub(crate) fn ptr(&self) -> *const ort_sys::OrtCustomOp {
+		(self as *const Self).cast()
+	}
+
+	/// # Safety
+	///
+	/// `op` must have been returned by [`BoundOperator::ptr`], and the operator must still be alive.
+	unsafe fn from_ptr<'a>(op: *const ort_sys::OrtCustomOp) -> &'a Self {
+		&*op.cast::<Self>()
+	}
+}
+
+/// The state of a single node using a custom operator.
+struct Kernel {
+	operator: *const BoundOperator,
+	attributes: KernelAttributes
+}
+
+fn error_status(message: &str) -> ort_sys::OrtStatusPtr {
+	let message = CString::new(message.replace('\0', "")).expect("null bytes were removed");
+	ortsys![unsafe CreateStatus(ort_sys::OrtErrorCode::ORT_RUNTIME_EXCEPTION, message.as_ptr())]
+}
+
+extern_system_fn! {
+	unsafe fn get_name(op: *const ort_sys::OrtCustomOp) -> *const c_char {
+		BoundOperator::from_ptr(op).name.as_ptr()
+	}
+}
+
+extern_system_fn! {
+	unsafe fn get_execution_provider_type(_: *const ort_sys::OrtCustomOp) -> *const c_char {
+		// the CPU execution provider
+		ptr::null()
+	}
+}
+
+extern_system_fn! {
+	unsafe fn get_input_type(op: *const ort_sys::OrtCustomOp, index: ort_sys::size_t) -> ort_sys::ONNXTensorElementDataType {
+		// unwinding out of a callback would abort, so report unknown indices as undefined instead of panicking
+		match BoundOperator::from_ptr(op).inputs.get(index) {
+			Some(input) => input.ty.into(),
+			None => ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED
+		}
+	}
+}
+
+extern_system_fn! {
+	unsafe fn get_input_type_count(op: *const ort_sys::OrtCustomOp) -> ort_sys::size_t {
+		BoundOperator::from_ptr(op).inputs.len() as _
+	}
+}
+
+extern_system_fn! {
+	unsafe fn get_output_type(op: *const ort_sys::OrtCustomOp, index: ort_sys::size_t) -> ort_sys::ONNXTensorElementDataType {
+		// unwinding out of a callback would abort, so report unknown indices as undefined instead of panicking
+		match BoundOperator::from_ptr(op).outputs.get(index) {
+			Some(output) => output.ty.into(),
+			None => ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED
+		}
+	}
+}
+
+extern_system_fn! {
+	unsafe fn get_output_type_count(op: *const ort_sys::OrtCustomOp) -> ort_sys::size_t {
+		BoundOperator::from_ptr(op).outputs.len() as _
+	}
+}
+
+extern_system_fn! {
+	unsafe fn get_input_characteristic(op: *const ort_sys::OrtCustomOp, index: ort_sys::size_t) -> ort_sys::OrtCustomOpInputOutputCharacteristic {
+		match BoundOperator::from_ptr(op).inputs.get(index) {
+			Some(input) => input.characteristic.into(),
+			None => ort_sys::OrtCustomOpInputOutputCharacteristic::INPUT_OUTPUT_REQUIRED
+		}
+	}
+}
+
+extern_system_fn! {
+	unsafe fn get_output_characteristic(op: *const ort_sys::OrtCustomOp, index: ort_sys::size_t) -> ort_sys::OrtCustomOpInputOutputCharacteristic {
+		match BoundOperator::from_ptr(op).outputs.get(index) {
+			Some(output) => output.characteristic.into(),
+			None => ort_sys::OrtCustomOpInputOutputCharacteristic::INPUT_OUTPUT_REQUIRED
+		}
+	}
+}
+
+extern_system_fn! {
+	unsafe fn get_input_memory_type(_: *const ort_sys::OrtCustomOp, _: ort_sys::size_t) -> ort_sys::OrtMemType {
+		ort_sys::OrtMemType::OrtMemTypeDefault
+	}
+}
+
+extern_system_fn! {
+	unsafe fn get_variadic_min_arity(_: *const ort_sys::OrtCustomOp) -> c_int {
+		1
+	}
+}
+
+extern_system_fn! {
+	unsafe fn get_variadic_homogeneity(_: *const ort_sys::OrtCustomOp) -> c_int {
+		1
+	}
+}
+
+extern_system_fn! {
+	unsafe fn create_kernel(
+		op: *const ort_sys::OrtCustomOp,
+		_: *const ort_sys::OrtApi,
+		info: *const ort_sys::OrtKernelInfo,
+		kernel: *mut *mut c_void
+	) -> ort_sys::OrtStatusPtr {
+		// `info` is only valid during this call, so the kernel keeps a copy to read attributes from
+		let mut info_copy: *mut ort_sys::OrtKernelInfo = ptr::null_mut();
+		let status = ortsys![unsafe CopyKernelInfo(info, &mut info_copy)];
+		if !status.is_null() {
+			return status;
+		}
+
+		*kernel = Box::into_raw(Box::new(Kernel {
+			operator: op.cast(),
+			attributes: KernelAttributes::from_raw(info_copy)
+		}))
+		.cast();
+		ptr::null_mut()
+	}
+}
+
+extern_system_fn! {
+	unsafe fn kernel_compute(kernel: *mut c_void, context: *mut ort_sys::OrtKernelContext) -> ort_sys::OrtStatusPtr {
+		let kernel = &*kernel.cast::<Kernel>();
+		let ctx = KernelContext::new(context, &kernel.attributes);
+		// unwinding into ONNX Runtime is undefined behavior, so panics are reported as errors instead
+		match panic::catch_unwind(AssertUnwindSafe(|| (*kernel.operator).operator.compute(&ctx))) {
+			Ok(Ok(())) => ptr::null_mut(),
+			Ok(Err(e)) => error_status(&e.to_string()),
+			Err(_) => error_status(&format!("custom operator `{}` panicked", (*kernel.operator).name()))
+		}
+	}
+}
+
+extern_system_fn! {
+	unsafe fn kernel_destroy(kernel: *mut c_void) {
+		drop(Box::from_raw(kernel.cast::<Kernel>()));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{KernelContext, TensorElementDataType};
+
+	struct Identity;
+
+	impl CustomOp for Identity {
+		fn name(&self) -> &str {
+			"Identity"
+		}
+
+		fn inputs(&self) -> Vec<OperatorInput> {
+			vec![OperatorInput::required(TensorElementDataType::Float32)]
+		}
+
+		fn outputs(&self) -> Vec<OperatorOutput> {
+			vec![OperatorOutput::optional(TensorElementDataType::Float32)]
+		}
+
+		fn compute(&self, _: &KernelContext<'_>) -> Result<()> {
+			Ok(())
+		}
+	}
+
+	#[test]
+	fn out_of_range_indices() -> Result<()> {
+		let operator = BoundOperator::new(Identity)?;
+		let op = operator.ptr();
+		unsafe {
+			assert_eq!(get_input_type(op, 0), ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_FLOAT);
+			assert_eq!(get_input_type(op, 1), ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED);
+			assert_eq!(get_output_type(op, 1), ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED);
+			assert_eq!(get_output_characteristic(op, 0), ort_sys::OrtCustomOpInputOutputCharacteristic::INPUT_OUTPUT_OPTIONAL);
+			assert_eq!(get_input_characteristic(op, 1), ort_sys::OrtCustomOpInputOutputCharacteristic::INPUT_OUTPUT_REQUIRED);
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,89 @@
+use crate::tensor::TensorElementDataType;
+
+/// Whether an input or output of a custom operator must be present.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputOutputCharacteristic {
+	/// The input or output must be present.
+	Required,
+	/// The input or output may be omitted.
+	Optional,
+	/// The input or output may occur one or more times. Only the last input or output of an operator can be variadic,
+	/// and all of its occurrences must be of the same type.
+	Variadic
+}
+
+impl From<InputOutputCharacteristic> for ort_sys::OrtCustomOpInputOutputCharacteristic {
+	fn from(val: InputOutputCharacteristic) -> Self {
+		match val {
+			InputOutputCharacteristic::Required => ort_sys::OrtCustomOpInputOutputCharacteristic::INPUT_OUTPUT_REQUIRED,
+			InputOutputCharacteristic::Optional => ort_sys::OrtCustomOpInputOutputCharacteristic::INPUT_OUTPUT_OPTIONAL,
+			InputOutputCharacteristic::Variadic => ort_sys::OrtCustomOpInputOutputCharacteristic::INPUT_OUTPUT_VARIADIC
+		}
+	}
+}
+
+/// Describes an input of a custom operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OperatorInput {
+	pub(crate) characteristic: InputOutputCharacteristic,
+	pub(crate) ty: TensorElementDataType
+}
+
+impl OperatorInput {
+	/// A required input with elements of type `ty`.
+	pub fn required(ty: TensorElementDataType) -> Self {
+		Self {
+			characteristic: InputOutputCharacteristic::Required,
+			ty
+		}
+	}
+
+	/// An optional input with elements of type `ty`.
+	pub fn optional(ty: TensorElementDataType) -> Self {
+		Self {
+			characteristic: InputOutputCharacteristic::Optional,
+			ty
+		}
+	}
+
+	/// A variadic input with elements of type `ty`.
+	pub fn variadic(ty: TensorElementDataType) -> Self {
+		Self {
+			characteristic: InputOutputCharacteristic::Variadic,
+			ty
+		}
+	}
+}
+
+/// Describes an output of a custom operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OperatorOutput {
+	pub(crate) characteristic: InputOutputCharacteristic,
+	pub(crate) ty: TensorElementDataType
+}
+
+impl OperatorOutput {
+	/// A required output with elements of type `ty`.
+	pub fn required(ty: TensorElementDataType) -> Self {
+		Self {
+			characteristic: InputOutputCharacteristic::Required,
+			ty
+		}
+	}
+
+	/// An optional output with elements of type `ty`.
+	pub fn optional(ty: TensorElementDataType) -> Self {
+		Self {
+			characteristic: InputOutputCharacteristic::Optional,
+			ty
+		}
+	}
+
+	/// A variadic output with elements of type `ty`.
+	pub fn variadic(ty: TensorElementDataType) -> Self {
+		Self {
+			characteristic: InputOutputCharacteristic::Variadic,
+			ty
+		}
+	}
+}
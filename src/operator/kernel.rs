@@ -0,0 +1,210 @@
+#[cfg(feature = "ndarray")]
+use std::cell::RefCell;
+use std::{
+	ffi::{CStr, CString},
+	os::raw::c_char,
+	ptr
+};
+
+#[cfg(feature = "ndarray")]
+use ndarray::{ArrayViewD, ArrayViewMutD, IxDyn};
+
+#[cfg(feature = "ndarray")]
+use crate::tensor::{IntoTensorElementDataType, TensorElementDataType};
+use crate::{char_p_to_string, ortsys, Error, Result};
+
+/// The attributes of a node which uses a custom operator.
+#[derive(Debug)]
+pub struct KernelAttributes {
+	ptr: *mut ort_sys::OrtKernelInfo
+}
+
+impl KernelAttributes {
+	/// Takes ownership of a kernel info copied with `CopyKernelInfo`.
+	pub(crate) fn from_raw(ptr: *mut ort_sys::OrtKernelInfo) -> Self {
+		Self { ptr }
+	}
+
+	/// Reads the attribute named `name`. Attributes can be read as `f32`, `i64`, `String`, `Vec<f32>`, or `Vec<i64>`.
+	///
+	/// Returns an error if the node has no such attribute, or if the attribute is of a different type.
+	pub fn get<T: KernelAttribute>(&self, name: impl AsRef<str>) -> Result<T> {
+		let name = CString::new(name.as_ref())?;
+		T::get(self, &name)
+	}
+}
+
+impl Drop for KernelAttributes {
+	fn drop(&mut self) {
+		ortsys![unsafe ReleaseKernelInfo(self.ptr)];
+	}
+}
+
+/// A type which can be read from the attributes of a node with [`KernelAttributes::get`].
+pub trait KernelAttribute: Sized {
+	#[doc(hidden)]
+	fn get(attributes: &KernelAttributes, name: &CStr) -> Result<Self>;
+}
+
+impl KernelAttribute for f32 {
+	fn get(attributes: &KernelAttributes, name: &CStr) -> Result<Self> {
+		let (info, name) = (attributes.ptr, name.as_ptr());
+		let mut value = 0.0;
+		ortsys![unsafe KernelInfoGetAttribute_float(info, name, &mut value) -> Error::GetKernelAttribute];
+		Ok(value)
+	}
+}
+
+impl KernelAttribute for i64 {
+	fn get(attributes: &KernelAttributes, name: &CStr) -> Result<Self> {
+		let (info, name) = (attributes.ptr, name.as_ptr());
+		let mut value = 0;
+		ortsys![unsafe KernelInfoGetAttribute_int64(info, name, &mut value) -> Error::GetKernelAttribute];
+		Ok(value)
+	}
+}
+
+impl KernelAttribute for String {
+	fn get(attributes: &KernelAttributes, name: &CStr) -> Result<Self> {
+		let (info, name) = (attributes.ptr, name.as_ptr());
+		// the first call only queries the length of the string, including its null terminator
+		let mut size = 0;
+		ortsys![unsafe KernelInfoGetAttribute_string(info, name, ptr::null_mut(), &mut size) -> Error::GetKernelAttribute];
+		let mut buffer: Vec<c_char> = vec![0; size as _];
+		ortsys![unsafe KernelInfoGetAttribute_string(info, name, buffer.as_mut_ptr(), &mut size) -> Error::GetKernelAttribute];
+		char_p_to_string(buffer.as_ptr())
+	}
+}
+
+impl KernelAttribute for Vec<f32> {
+	fn get(attributes: &KernelAttributes, name: &CStr) -> Result<Self> {
+		let (info, name) = (attributes.ptr, name.as_ptr());
+		let mut size = 0;
+		ortsys![unsafe KernelInfoGetAttributeArray_float(info, name, ptr::null_mut(), &mut size) -> Error::GetKernelAttribute];
+		let mut values = vec![0.0; size as _];
+		ortsys![unsafe KernelInfoGetAttributeArray_float(info, name, values.as_mut_ptr(), &mut size) -> Error::GetKernelAttribute];
+		Ok(values)
+	}
+}
+
+impl KernelAttribute for Vec<i64> {
+	fn get(attributes: &KernelAttributes, name: &CStr) -> Result<Self> {
+		let (info, name) = (attributes.ptr, name.as_ptr());
+		let mut size = 0;
+		ortsys![unsafe KernelInfoGetAttributeArray_int64(info, name, ptr::null_mut(), &mut size) -> Error::GetKernelAttribute];
+		let mut values = vec![0; size as _];
+		ortsys![unsafe KernelInfoGetAttributeArray_int64(info, name, values.as_mut_ptr(), &mut size) -> Error::GetKernelAttribute];
+		Ok(values)
+	}
+}
+
+/// Provides access to the inputs & outputs of a custom operator during [`CustomOp::compute`](crate::CustomOp::compute).
+#[derive(Debug)]
+pub struct KernelContext<'k> {
+	ptr: *mut ort_sys::OrtKernelContext,
+	attributes: &'k KernelAttributes,
+	#[cfg(feature = "ndarray")]
+	borrowed_outputs: RefCell<Vec<usize>>
+}
+
+impl<'k> KernelContext<'k> {
+	pub(crate) fn new(ptr: *mut ort_sys::OrtKernelContext, attributes: &'k KernelAttributes) -> Self {
+		Self {
+			ptr,
+			attributes,
+			#[cfg(feature = "ndarray")]
+			borrowed_outputs: RefCell::new(Vec::new())
+		}
+	}
+
+	/// Returns the attributes of the node being computed.
+	pub fn attributes(&self) -> &KernelAttributes {
+		self.attributes
+	}
+
+	/// Returns the number of inputs passed to the node, including variadic inputs.
+	pub fn num_inputs(&self) -> Result<usize> {
+		let mut count = 0;
+		ortsys![unsafe KernelContext_GetInputCount(self.ptr, &mut count) -> Error::GetInOutCount];
+		Ok(count as _)
+	}
+
+	/// Returns the number of outputs of the node, including variadic outputs.
+	pub fn num_outputs(&self) -> Result<usize> {
+		let mut count = 0;
+		ortsys![unsafe KernelContext_GetOutputCount(self.ptr, &mut count) -> Error::GetInOutCount];
+		Ok(count as _)
+	}
+
+	/// Returns a view of the input at `index`, or `None` if the input is optional and was not provided.
+	#[cfg(feature = "ndarray")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
+	pub fn input<T: IntoTensorElementDataType>(&self, index: usize) -> Result<Option<ArrayViewD<'_, T>>> {
+		let mut value_ptr: *const ort_sys::OrtValue = ptr::null();
+		ortsys![unsafe KernelContext_GetInput(self.ptr, index as _, &mut value_ptr) -> Error::GetKernelInput];
+		if value_ptr.is_null() {
+			return Ok(None);
+		}
+
+		let (shape, data) = tensor_data::<T>(value_ptr.cast_mut())?;
+		Ok(Some(unsafe { ArrayViewD::from_shape_ptr(IxDyn(&shape), data.cast_const()) }))
+	}
+
+	/// Allocates the output at `index` with the given shape & returns a mutable view of it, or `None` if the output is
+	/// optional and not used by the graph.
+	///
+	/// Each output can only be allocated once per computation.
+	#[cfg(feature = "ndarray")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
+	pub fn output<T: IntoTensorElementDataType>(&self, index: usize, shape: &[usize]) -> Result<Option<ArrayViewMutD<'_, T>>> {
+		if self.borrowed_outputs.borrow().contains(&index) {
+			return Err(Error::KernelOutputAlreadyAllocated(index));
+		}
+
+		let dims: Vec<i64> = shape.iter().map(|&dim| dim as i64).collect();
+		let mut value_ptr: *mut ort_sys::OrtValue = ptr::null_mut();
+		ortsys![unsafe KernelContext_GetOutput(self.ptr, index as _, dims.as_ptr(), dims.len() as _, &mut value_ptr) -> Error::GetKernelOutput];
+		if value_ptr.is_null() {
+			return Ok(None);
+		}
+		self.borrowed_outputs.borrow_mut().push(index);
+
+		let (_, data) = tensor_data::<T>(value_ptr)?;
+		Ok(Some(unsafe { ArrayViewMutD::from_shape_ptr(IxDyn(shape), data) }))
+	}
+}
+
+/// Returns the shape of a tensor & a pointer to its data, checking that its elements are of type `T`.
+#[cfg(feature = "ndarray")]
+fn tensor_data<T: IntoTensorElementDataType>(value_ptr: *mut ort_sys::OrtValue) -> Result<(Vec<usize>, *mut T)> {
+	let requested = T::into_tensor_element_data_type();
+	if requested == TensorElementDataType::String {
+		return Err(Error::KernelStringTensor);
+	}
+
+	let mut tensor_info_ptr: *mut ort_sys::OrtTensorTypeAndShapeInfo = ptr::null_mut();
+	ortsys![unsafe GetTensorTypeAndShape(value_ptr, &mut tensor_info_ptr) -> Error::GetTensorTypeAndShape; nonNull(tensor_info_ptr)];
+
+	let res = (|| {
+		let mut type_sys = ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED;
+		ortsys![unsafe GetTensorElementType(tensor_info_ptr, &mut type_sys) -> Error::GetTensorElementType];
+		if type_sys == ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED {
+			return Err(Error::UndefinedTensorElementType);
+		}
+		let actual: TensorElementDataType = type_sys.into();
+		if actual != requested {
+			return Err(Error::DataTypeMismatch { actual, requested });
+		}
+
+		let mut num_dims = 0;
+		ortsys![unsafe GetDimensionsCount(tensor_info_ptr, &mut num_dims) -> Error::GetDimensionsCount];
+		let mut dims: Vec<i64> = vec![0; num_dims as _];
+		ortsys![unsafe GetDimensions(tensor_info_ptr, dims.as_mut_ptr(), num_dims as _) -> Error::GetDimensions];
+
+		let mut data: *mut T = ptr::null_mut();
+		ortsys![unsafe GetTensorMutableData(value_ptr, (&mut data as *mut *mut T).cast()) -> Error::GetTensorMutableData; nonNull(data)];
+		Ok((dims.into_iter().map(|dim| dim as usize).collect(), data))
+	})();
+	ortsys![unsafe ReleaseTensorTypeAndShapeInfo(tensor_info_ptr)];
+	res
+}
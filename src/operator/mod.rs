@@ -0,0 +1,130 @@
+//! Contains the [`CustomOp`] trait for implementing custom operators in Rust, and [`OperatorDomain`] for registering
+//! them with a session.
+
+use std::{ffi::CString, fmt, ptr};
+
+pub(crate) mod bound;
+pub(crate) mod io;
+pub(crate) mod kernel;
+
+use self::bound::BoundOperator;
+pub use self::{
+	io::{InputOutputCharacteristic, OperatorInput, OperatorOutput},
+	kernel::{KernelAttribute, KernelAttributes, KernelContext}
+};
+use crate::{ortsys, Error, Result};
+
+/// A custom operator implemented in Rust.
+///
+/// Custom operators are grouped into an [`OperatorDomain`], which is registered with a session via
+/// [`SessionBuilder::with_operators`](crate::SessionBuilder::with_operators). Nodes use a custom operator if their
+/// domain & `op_type` match the operator's domain & [`CustomOp::name`]. Custom operators run on the CPU.
+///
+/// ```
+/// # use ort::{CustomOp, KernelContext, OperatorInput, OperatorOutput, TensorElementDataType};
+/// /// Multiplies its input by the node's `factor` attribute.
+/// struct Scale;
+///
+/// impl CustomOp for Scale {
+/// 	fn name(&self) -> &str {
+/// 		"Scale"
+/// 	}
+///
+/// 	fn inputs(&self) -> Vec<OperatorInput> {
+/// 		vec![OperatorInput::required(TensorElementDataType::Float32)]
+/// 	}
+///
+/// 	fn outputs(&self) -> Vec<OperatorOutput> {
+/// 		vec![OperatorOutput::required(TensorElementDataType::Float32)]
+/// 	}
+///
+/// 	fn compute(&self, ctx: &KernelContext<'_>) -> ort::Result<()> {
+/// 		let factor: f32 = ctx.attributes().get("factor")?;
+/// 		let x = ctx.input::<f32>(0)?.expect("input is required");
+/// 		if let Some(mut y) = ctx.output::<f32>(0, x.shape())? {
+/// 			y.assign(&(&x * factor));
+/// 		}
+/// 		Ok(())
+/// 	}
+/// }
+/// ```
+pub trait CustomOp: Send + Sync + 'static {
+	/// Returns the name of the operator, i.e. the `op_type` of nodes using it.
+	fn name(&self) -> &str;
+
+	/// Returns the inputs of the operator.
+	fn inputs(&self) -> Vec<OperatorInput>;
+
+	/// Returns the outputs of the operator.
+	fn outputs(&self) -> Vec<OperatorOutput>;
+
+	/// Computes the outputs of a node using this operator. This may be called from multiple threads at once.
+	///
+	/// Errors (and panics) are reported to ONNX Runtime, causing the run to fail.
+	fn compute(&self, ctx: &KernelContext<'_>) -> Result<()>;
+}
+
+/// A domain of [`CustomOp`]s, registered with a session via
+/// [`SessionBuilder::with_operators`](crate::SessionBuilder::with_operators).
+///
+/// ```no_run
+/// # use ort::{OperatorDomain, Session};
+/// # fn main() -> ort::Result<()> {
+/// # struct Scale;
+/// # impl ort::CustomOp for Scale {
+/// # 	fn name(&self) -> &str { "Scale" }
+/// # 	fn inputs(&self) -> Vec<ort::OperatorInput> { vec![] }
+/// # 	fn outputs(&self) -> Vec<ort::OperatorOutput> { vec![] }
+/// # 	fn compute(&self, _: &ort::KernelContext<'_>) -> ort::Result<()> { Ok(()) }
+/// # }
+/// let builder = Session::builder()?.with_operators(OperatorDomain::new("com.example")?.with_operator(Scale)?)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct OperatorDomain {
+	ptr: *mut ort_sys::OrtCustomOpDomain,
+	// boxed, as ONNX Runtime holds pointers to each operator
+	#[allow(clippy::vec_box)]
+	operators: Vec<Box<BoundOperator>>
+}
+
+unsafe impl Send for OperatorDomain {}
+// Operators are only read by ONNX Runtime after being added, and `CustomOp` requires `Sync`.
+unsafe impl Sync for OperatorDomain {}
+
+impl fmt::Debug for OperatorDomain {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("OperatorDomain")
+			.field("operators", &self.operators.iter().map(|operator| operator.name()).collect::<Vec<_>>())
+			.finish()
+	}
+}
+
+impl OperatorDomain {
+	/// Creates a new, empty operator domain with the given name, e.g. `com.example`. Nodes must specify this domain to
+	/// use its operators.
+	pub fn new(name: impl AsRef<str>) -> Result<Self> {
+		let name = CString::new(name.as_ref())?;
+		let mut ptr: *mut ort_sys::OrtCustomOpDomain = ptr::null_mut();
+		ortsys![unsafe CreateCustomOpDomain(name.as_ptr(), &mut ptr) -> Error::CreateOperatorDomain; nonNull(ptr)];
+		Ok(Self { ptr, operators: Vec::new() })
+	}
+
+	/// Adds an operator to the domain.
+	pub fn with_operator<O: CustomOp>(mut self, operator: O) -> Result<Self> {
+		let operator = BoundOperator::new(operator)?;
+		ortsys![unsafe CustomOpDomain_Add(self.ptr, operator.ptr()) -> Error::AddOperator];
+		self.operators.push(operator);
+		Ok(self)
+	}
+
+	pub(crate) fn ptr(&self) -> *mut ort_sys::OrtCustomOpDomain {
+		self.ptr
+	}
+}
+
+impl Drop for OperatorDomain {
+	fn drop(&mut self) {
+		ortsys![unsafe ReleaseCustomOpDomain(self.ptr)];
+	}
+}
@@ -0,0 +1,410 @@
+use std::{
+	ffi::c_void,
+	ptr,
+	sync::{mpsc, Arc, Mutex},
+	thread::{self, JoinHandle},
+	time::{Duration, Instant}
+};
+
+use super::{input::validate_inputs, Session, SessionInputs, SessionOutputs};
+use crate::{ortsys, Error, Result, TensorElementDataType, Value, ValueType};
+
+/// Returns the first dynamic axis of a tensor input/output, or [`Error::NotBatchable`] if it has none or its elements
+/// are not of a fixed size.
+fn batch_axis(name: &str, value_type: &ValueType) -> Result<usize> {
+	match value_type {
+		ValueType::Tensor { ty, dimensions, .. } if ty.byte_size().is_some() => dimensions
+			.iter()
+			.position(|&d| d < 0)
+			.ok_or_else(|| Error::NotBatchable(name.to_string())),
+		_ => Err(Error::NotBatchable(name.to_string()))
+	}
+}
+
+/// Returns a pointer to the data of a CPU tensor.
+fn tensor_data_ptr(value: &Value) -> Result<*mut u8> {
+	let mut data_ptr: *mut c_void = ptr::null_mut();
+	ortsys![unsafe GetTensorMutableData(value.ptr(), &mut data_ptr) -> Error::GetTensorMutableData];
+	Ok(data_ptr as *mut u8)
+}
+
+/// Allocates an uninitialized tensor with the session's allocator.
+fn allocate_tensor(session: &Session, shape: &[i64], dtype: TensorElementDataType) -> Result<Value> {
+	let mut value_ptr: *mut ort_sys::OrtValue = ptr::null_mut();
+	ortsys![
+		unsafe CreateTensorAsOrtValue(session.inner.allocator.ptr, shape.as_ptr(), shape.len() as _, dtype.into(), &mut value_ptr) -> Error::CreateTensor;
+		nonNull(value_ptr)
+	];
+	Ok(unsafe { Value::from_raw(value_ptr, Arc::clone(&session.inner)) })
+}
+
+/// Concatenates tensors of the same type along `axis`.
+fn concat(session: &Session, values: &[&Value], axis: usize) -> Result<Value> {
+	let dtype = values[0].dtype()?;
+	let element_size = dtype.byte_size().ok_or_else(|| Error::NotBatchable(format!("{dtype:?}")))?;
+	let shapes = values.iter().map(|v| v.shape()).collect::<Result<Vec<_>>>()?;
+
+	let mut shape = shapes[0].clone();
+	shape[axis] = shapes.iter().map(|s| s[axis]).sum();
+	let outer = shape[..axis].iter().product::<i64>() as usize;
+	let inner = shape[axis + 1..].iter().product::<i64>() as usize * element_size;
+
+	let output = allocate_tensor(session, &shape, dtype)?;
+	let dst = tensor_data_ptr(&output)?;
+	let srcs = values.iter().map(|v| tensor_data_ptr(v)).collect::<Result<Vec<_>>>()?;
+
+	let mut offset = 0;
+	for o in 0..outer {
+		for (src, src_shape) in srcs.iter().zip(&shapes) {
+			let block = src_shape[axis] as usize * inner;
+			if block > 0 {
+				unsafe { ptr::copy_nonoverlapping(src.add(o * block), dst.add(offset), block) };
+			}
+			offset += block;
+		}
+	}
+	Ok(output)
+}
+
+/// Splits a tensor along `axis` into chunks of the given sizes.
+fn split(session: &Session, name: &str, value: &Value, axis: usize, sizes: &[usize]) -> Result<Vec<Value>> {
+	let dtype = value.dtype()?;
+	let element_size = dtype.byte_size().ok_or_else(|| Error::NotBatchable(name.to_string()))?;
+	let shape = value.shape()?;
+	let total: usize = sizes.iter().sum();
+	if shape.get(axis) != Some(&(total as i64)) {
+		return Err(Error::BatchOutputMismatch(name.to_string()));
+	}
+
+	let outer = shape[..axis].iter().product::<i64>() as usize;
+	let inner = shape[axis + 1..].iter().product::<i64>() as usize * element_size;
+	let src = tensor_data_ptr(value)?;
+
+	let mut parts = Vec::with_capacity(sizes.len());
+	let mut start = 0;
+	for &size in sizes {
+		let mut part_shape = shape.clone();
+		part_shape[axis] = size as i64;
+		let part = allocate_tensor(session, &part_shape, dtype)?;
+		let dst = tensor_data_ptr(&part)?;
+		let block = size * inner;
+		if block > 0 {
+			for o in 0..outer {
+				unsafe { ptr::copy_nonoverlapping(src.add((o * total + start) * inner), dst.add(o * block), block) };
+			}
+		}
+		parts.push(part);
+		start += size;
+	}
+	Ok(parts)
+}
+
+enum RequestInputs {
+	Owned(Vec<Value>),
+	Borrowed(&'static [Value])
+}
+
+impl RequestInputs {
+	fn as_slice(&self) -> &[Value] {
+		match self {
+			RequestInputs::Owned(values) => values,
+			RequestInputs::Borrowed(values) => values
+		}
+	}
+}
+
+struct BatchRequest {
+	/// Inputs in the order of [`Session::inputs`].
+	inputs: RequestInputs,
+	shapes: Vec<Vec<i64>>,
+	dtypes: Vec<TensorElementDataType>,
+	/// Number of samples in this request along the batch axis.
+	batch_size: usize,
+	reply: mpsc::Sender<Result<Vec<Value>>>
+}
+
+impl BatchRequest {
+	/// Whether the inputs of `other` can be concatenated with the inputs of this request.
+	fn is_compatible(&self, other: &BatchRequest, input_axes: &[usize]) -> bool {
+		self.dtypes == other.dtypes
+			&& self
+				.shapes
+				.iter()
+				.zip(&other.shapes)
+				.zip(input_axes)
+				.all(|((a, b), &axis)| a.len() == b.len() && a.iter().zip(b).enumerate().all(|(i, (a, b))| i == axis || a == b))
+	}
+}
+
+/// Takes the outputs of a run in the order of [`Session::outputs`].
+fn take_outputs(session: &Session, mut outputs: SessionOutputs<'_>) -> Vec<Value> {
+	session
+		.outputs
+		.iter()
+		.map(|output| outputs.remove(output.name.as_str()).expect("session outputs are missing an output"))
+		.collect()
+}
+
+fn run_single(session: &Session, request: &BatchRequest) -> Result<Vec<Value>> {
+	let input_names: Vec<&str> = session.inputs.iter().map(|input| input.name.as_str()).collect();
+	Ok(take_outputs(session, session.run_inner(&input_names, request.inputs.as_slice(), None, None)?))
+}
+
+fn run_batched(session: &Session, input_axes: &[usize], output_axes: &[usize], batch: &[BatchRequest]) -> Result<Vec<Vec<Value>>> {
+	let input_names: Vec<&str> = session.inputs.iter().map(|input| input.name.as_str()).collect();
+	let inputs = input_axes
+		.iter()
+		.enumerate()
+		.map(|(i, &axis)| concat(session, &batch.iter().map(|request| &request.inputs.as_slice()[i]).collect::<Vec<_>>(), axis))
+		.collect::<Result<Vec<_>>>()?;
+	let outputs = take_outputs(session, session.run_inner(&input_names, &inputs, None, None)?);
+
+	let sizes: Vec<usize> = batch.iter().map(|request| request.batch_size).collect();
+	let mut results: Vec<Vec<Value>> = batch.iter().map(|_| Vec::with_capacity(outputs.len())).collect();
+	for ((output, value), &axis) in session.outputs.iter().zip(&outputs).zip(output_axes) {
+		for (result, part) in results.iter_mut().zip(split(session, &output.name, value, axis, &sizes)?) {
+			result.push(part);
+		}
+	}
+	Ok(results)
+}
+
+fn run_batch(session: &Session, input_axes: &[usize], output_axes: &[usize], batch: Vec<BatchRequest>) {
+	if batch.len() > 1 {
+		match run_batched(session, input_axes, output_axes, &batch) {
+			Ok(results) => {
+				for (request, result) in batch.iter().zip(results) {
+					let _ = request.reply.send(Ok(result));
+				}
+				return;
+			}
+			Err(e) => tracing::warn!("batched run of {} requests failed, running them individually: {e}", batch.len())
+		}
+	}
+
+	// run requests individually so that errors are attributed to the request that caused them
+	for request in &batch {
+		let _ = request.reply.send(run_single(session, request));
+	}
+}
+
+fn batch_worker(
+	session: Arc<Session>,
+	receiver: mpsc::Receiver<BatchRequest>,
+	input_axes: Vec<usize>,
+	output_axes: Vec<usize>,
+	max_batch_size: usize,
+	max_wait: Duration
+) {
+	let mut pending: Option<BatchRequest> = None;
+	loop {
+		let first = match pending.take() {
+			Some(request) => request,
+			None => match receiver.recv() {
+				Ok(request) => request,
+				Err(_) => return
+			}
+		};
+
+		let deadline = Instant::now() + max_wait;
+		let mut batch_size = first.batch_size;
+		let mut batch = vec![first];
+		while batch_size < max_batch_size {
+			match receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+				Ok(request) => {
+					if batch_size + request.batch_size > max_batch_size || !batch[0].is_compatible(&request, &input_axes) {
+						pending = Some(request);
+						break;
+					}
+					batch_size += request.batch_size;
+					batch.push(request);
+				}
+				Err(_) => break
+			}
+		}
+
+		run_batch(&session, &input_axes, &output_axes, batch);
+	}
+}
+
+/// Builder for a [`BatchRunner`]. See [`BatchRunner::builder`].
+#[derive(Debug)]
+pub struct BatchRunnerBuilder {
+	session: Arc<Session>,
+	max_batch_size: usize,
+	max_wait: Duration
+}
+
+impl BatchRunnerBuilder {
+	/// Sets the maximum number of samples (along the batch axis) which will be combined into a single run. Defaults to
+	/// 32.
+	pub fn with_max_batch_size(mut self, max_batch_size: usize) -> Self {
+		self.max_batch_size = max_batch_size.max(1);
+		self
+	}
+
+	/// Sets the maximum amount of time to wait for more requests after the first request of a batch is received.
+	/// Defaults to 5 milliseconds.
+	pub fn with_max_wait(mut self, max_wait: Duration) -> Self {
+		self.max_wait = max_wait;
+		self
+	}
+
+	/// Starts the batch runner.
+	///
+	/// Returns [`Error::NotBatchable`] if any of the session's inputs or outputs is not a tensor with a dynamic
+	/// dimension, or is a string tensor.
+	pub fn build(self) -> Result<BatchRunner> {
+		let input_axes = self
+			.session
+			.inputs
+			.iter()
+			.map(|input| batch_axis(&input.name, &input.input_type))
+			.collect::<Result<Vec<_>>>()?;
+		let output_axes = self
+			.session
+			.outputs
+			.iter()
+			.map(|output| batch_axis(&output.name, &output.output_type))
+			.collect::<Result<Vec<_>>>()?;
+
+		let (sender, receiver) = mpsc::channel();
+		let worker = {
+			let session = Arc::clone(&self.session);
+			let input_axes = input_axes.clone();
+			thread::Builder::new()
+				.name("ort-batch-runner".to_string())
+				.spawn(move || batch_worker(session, receiver, input_axes, output_axes, self.max_batch_size, self.max_wait))
+				.map_err(Error::SpawnBatchRunner)?
+		};
+
+		Ok(BatchRunner {
+			session: self.session,
+			input_axes,
+			sender: Mutex::new(Some(sender)),
+			worker: Some(worker)
+		})
+	}
+}
+
+/// Combines concurrent requests to a [`Session`] into batched runs.
+///
+/// Each request's inputs are concatenated along the first dynamic dimension of the corresponding model input (usually
+/// the batch dimension), the session is run once, and each output is split back along its first dynamic dimension. A
+/// batch is run once it reaches the maximum batch size, or once the maximum wait time has elapsed since its first
+/// request was received.
+///
+/// If a batched run fails, the requests are run individually so that each request receives its own result. All
+/// tensors must be in CPU memory.
+///
+/// ```no_run
+/// # use std::sync::Arc;
+/// # use ort::{BatchRunner, Session};
+/// # fn main() -> ort::Result<()> {
+/// let session = Arc::new(Session::builder()?.with_model_from_file("model.onnx")?);
+/// let runner = BatchRunner::builder(session).with_max_batch_size(16).build()?;
+/// // `run` can be called concurrently from many threads
+/// let outputs = runner.run(ort::inputs![ndarray::Array2::<f32>::zeros((1, 128))]?)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct BatchRunner {
+	session: Arc<Session>,
+	input_axes: Vec<usize>,
+	sender: Mutex<Option<mpsc::Sender<BatchRequest>>>,
+	worker: Option<JoinHandle<()>>
+}
+
+impl BatchRunner {
+	/// Creates a [`BatchRunnerBuilder`] for the given session.
+	pub fn builder(session: Arc<Session>) -> BatchRunnerBuilder {
+		BatchRunnerBuilder {
+			session,
+			max_batch_size: 32,
+			max_wait: Duration::from_millis(5)
+		}
+	}
+
+	/// Returns the session this runner runs.
+	pub fn session(&self) -> &Session {
+		&self.session
+	}
+
+	/// Submits a request to the next batch and blocks until its outputs are available.
+	pub fn run<const N: usize>(&self, input_values: impl Into<SessionInputs<'static, N>>) -> Result<SessionOutputs<'_>> {
+		let inputs = match input_values.into() {
+			SessionInputs::ValueSlice(values) => RequestInputs::Borrowed(values),
+			SessionInputs::ValueArray(values) => RequestInputs::Owned(values.into()),
+			SessionInputs::ValueMap(mut values) => {
+				let ordered = self
+					.session
+					.inputs
+					.iter()
+					.map(|input| values.remove(input.name.as_str()).ok_or_else(|| Error::MissingInput(input.name.clone())))
+					.collect::<Result<Vec<_>>>()?;
+				if let Some(name) = values.into_keys().next() {
+					return Err(Error::UnknownInput(name.into_owned()));
+				}
+				RequestInputs::Owned(ordered)
+			}
+		};
+
+		let input_names: Vec<&str> = self.session.inputs.iter().map(|input| input.name.as_str()).collect();
+		if self.session.validate_inputs {
+			validate_inputs(&self.session, &input_names, inputs.as_slice())?;
+		} else if inputs.as_slice().len() != input_names.len() {
+			return Err(Error::InputCountMismatch {
+				expected: input_names.len(),
+				actual: inputs.as_slice().len()
+			});
+		}
+
+		let shapes = inputs.as_slice().iter().map(|v| v.shape()).collect::<Result<Vec<_>>>()?;
+		let dtypes = inputs.as_slice().iter().map(|v| v.dtype()).collect::<Result<Vec<_>>>()?;
+		let mut batch_size = None;
+		for ((name, shape), &axis) in input_names.iter().zip(&shapes).zip(&self.input_axes) {
+			let size = *shape.get(axis).ok_or_else(|| Error::NotBatchable(name.to_string()))? as usize;
+			match batch_size {
+				None => batch_size = Some(size),
+				Some(expected) if expected != size => {
+					return Err(Error::InconsistentBatchSize {
+						name: name.to_string(),
+						expected,
+						actual: size
+					});
+				}
+				Some(_) => {}
+			}
+		}
+
+		let (reply, receiver) = mpsc::channel();
+		let request = BatchRequest {
+			inputs,
+			shapes,
+			dtypes,
+			batch_size: batch_size.unwrap_or(0),
+			reply
+		};
+		self.sender
+			.lock()
+			.expect("batch runner lock poisoned")
+			.as_ref()
+			.ok_or(Error::BatchRunnerStopped)?
+			.send(request)
+			.map_err(|_| Error::BatchRunnerStopped)?;
+
+		let outputs = receiver.recv().map_err(|_| Error::BatchRunnerStopped)??;
+		Ok(SessionOutputs::new(self.session.outputs.iter().map(|output| output.name.as_str()), outputs))
+	}
+}
+
+impl Drop for BatchRunner {
+	fn drop(&mut self) {
+		// closing the channel stops the worker once it has finished the requests it already received
+		drop(self.sender.lock().map(|mut sender| sender.take()));
+		if let Some(worker) = self.worker.take() {
+			let _ = worker.join();
+		}
+	}
+}
@@ -0,0 +1,106 @@
+use super::ModelFormat;
+
+/// Well-known session configuration entries, for use with
+/// [`SessionBuilder::with_config`](crate::SessionBuilder::with_config).
+///
+/// See ONNX Runtime's [`onnxruntime_session_options_config_keys.h`](https://github.com/microsoft/onnxruntime/blob/v1.16.3/include/onnxruntime/core/session/onnxruntime_session_options_config_keys.h)
+/// for more information on each entry. Entries not covered here can be set with
+/// [`SessionBuilder::with_config_entry`](crate::SessionBuilder::with_config_entry).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SessionConfig {
+	/// `session.load_model_format`: the format of the model to load. By default, the format is determined by the file
+	/// extension (for files) or by inspecting the bytes (for in-memory models).
+	LoadModelFormat(ModelFormat),
+	/// `session.save_model_format`: the format to save an optimized model in. By default, the format is determined by
+	/// the file extension of the path passed to
+	/// [`SessionBuilder::with_optimized_model_path`](crate::SessionBuilder::with_optimized_model_path).
+	SaveModelFormat(ModelFormat),
+	/// `session.use_ort_model_bytes_directly`: use the bytes of an in-memory `.ort` model directly instead of copying
+	/// them. The bytes must outlive the session.
+	UseOrtModelBytesDirectly(bool),
+	/// `session.use_ort_model_bytes_for_initializers`: use the bytes of an in-memory `.ort` model directly for
+	/// initializers. Requires [`SessionConfig::UseOrtModelBytesDirectly`].
+	UseOrtModelBytesForInitializers(bool),
+	/// `session.intra_op.allow_spinning`: whether intra-op threads spin while waiting for work. Disabling spinning
+	/// reduces CPU usage at the cost of latency. Defaults to `true`.
+	IntraOpAllowSpinning(bool),
+	/// `session.inter_op.allow_spinning`: whether inter-op threads spin while waiting for work. Defaults to `true`.
+	InterOpAllowSpinning(bool),
+	/// `session.disable_prepacking`: disables pre-packing of constant weights.
+	DisablePrepacking(bool),
+	/// `session.use_env_allocators`: use allocators registered with the environment instead of per-session allocators.
+	UseEnvAllocators(bool),
+	/// `session.set_denormal_as_zero`: treat denormal floats as zero, which can improve performance on some hardware.
+	SetDenormalAsZero(bool),
+	/// `session.disable_quant_qdq`: disables quantization-related QDQ node optimizations.
+	DisableQuantQdq(bool),
+	/// `session.disable_double_qdq_remover`: disables removal of redundant double QDQ node pairs.
+	DisableDoubleQdqRemover(bool),
+	/// `session.enable_quant_qdq_cleanup`: removes redundant Q/DQ node pairs after optimization.
+	EnableQuantQdqCleanup(bool),
+	/// `session.disable_aot_function_inlining`: disables ahead-of-time inlining of model-local functions.
+	DisableAotFunctionInlining(bool),
+	/// `session.use_device_allocator_for_initializers`: allocate initializers with the device allocator instead of an
+	/// arena, which can reduce memory usage.
+	UseDeviceAllocatorForInitializers(bool),
+	/// `session.intra_op_thread_affinities`: the processor affinities of intra-op threads, e.g. `"1,2,3;4,5"`.
+	IntraOpThreadAffinities(String)
+}
+
+impl SessionConfig {
+	/// Returns the configuration key of this entry.
+	pub fn key(&self) -> &'static str {
+		match self {
+			SessionConfig::LoadModelFormat(_) => "session.load_model_format",
+			SessionConfig::SaveModelFormat(_) => "session.save_model_format",
+			SessionConfig::UseOrtModelBytesDirectly(_) => "session.use_ort_model_bytes_directly",
+			SessionConfig::UseOrtModelBytesForInitializers(_) => "session.use_ort_model_bytes_for_initializers",
+			SessionConfig::IntraOpAllowSpinning(_) => "session.intra_op.allow_spinning",
+			SessionConfig::InterOpAllowSpinning(_) => "session.inter_op.allow_spinning",
+			SessionConfig::DisablePrepacking(_) => "session.disable_prepacking",
+			SessionConfig::UseEnvAllocators(_) => "session.use_env_allocators",
+			SessionConfig::SetDenormalAsZero(_) => "session.set_denormal_as_zero",
+			SessionConfig::DisableQuantQdq(_) => "session.disable_quant_qdq",
+			SessionConfig::DisableDoubleQdqRemover(_) => "session.disable_double_qdq_remover",
+			SessionConfig::EnableQuantQdqCleanup(_) => "session.enable_quant_qdq_cleanup",
+			SessionConfig::DisableAotFunctionInlining(_) => "session.disable_aot_function_inlining",
+			SessionConfig::UseDeviceAllocatorForInitializers(_) => "session.use_device_allocator_for_initializers",
+			SessionConfig::IntraOpThreadAffinities(_) => "session.intra_op_thread_affinities"
+		}
+	}
+
+	/// Returns the configuration value of this entry, as it is passed to ONNX Runtime.
+	pub fn value(&self) -> String {
+		match self {
+			SessionConfig::LoadModelFormat(format) | SessionConfig::SaveModelFormat(format) => format.as_str().to_string(),
+			SessionConfig::UseOrtModelBytesDirectly(enable)
+			| SessionConfig::UseOrtModelBytesForInitializers(enable)
+			| SessionConfig::IntraOpAllowSpinning(enable)
+			| SessionConfig::InterOpAllowSpinning(enable)
+			| SessionConfig::DisablePrepacking(enable)
+			| SessionConfig::UseEnvAllocators(enable)
+			| SessionConfig::SetDenormalAsZero(enable)
+			| SessionConfig::DisableQuantQdq(enable)
+			| SessionConfig::DisableDoubleQdqRemover(enable)
+			| SessionConfig::EnableQuantQdqCleanup(enable)
+			| SessionConfig::DisableAotFunctionInlining(enable)
+			| SessionConfig::UseDeviceAllocatorForInitializers(enable) => if *enable { "1" } else { "0" }.to_string(),
+			SessionConfig::IntraOpThreadAffinities(affinities) => affinities.clone()
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_session_config() {
+		assert_eq!(SessionConfig::IntraOpAllowSpinning(false).key(), "session.intra_op.allow_spinning");
+		assert_eq!(SessionConfig::IntraOpAllowSpinning(false).value(), "0");
+		assert_eq!(SessionConfig::DisablePrepacking(true).value(), "1");
+		assert_eq!(SessionConfig::LoadModelFormat(ModelFormat::Ort).value(), "ORT");
+		assert_eq!(SessionConfig::IntraOpThreadAffinities("1,2;3,4".to_string()).value(), "1,2;3,4");
+	}
+}
@@ -0,0 +1,254 @@
+use std::{collections::HashMap, ptr, str, sync::Arc};
+
+use crate::{tensor::TensorElementDataType, Error, Result, Value};
+
+/// An initializer whose data is stored in an external data file, as described by its `TensorProto`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct ExternalTensor {
+	pub name: String,
+	pub data_type: i32,
+	pub dims: Vec<i64>,
+	pub location: String,
+	pub offset: usize,
+	pub length: Option<usize>
+}
+
+/// A field of a protobuf message. Only the wire types used by the ONNX messages we read are distinguished.
+enum Field<'b> {
+	Varint(u64),
+	Bytes(&'b [u8]),
+	Fixed
+}
+
+/// A minimal reader for the protobuf wire format, so that external data can be located without depending on a full
+/// protobuf implementation.
+struct Reader<'b> {
+	bytes: &'b [u8]
+}
+
+impl<'b> Reader<'b> {
+	fn new(bytes: &'b [u8]) -> Self {
+		Self { bytes }
+	}
+
+	fn take(&mut self, len: usize) -> Result<&'b [u8]> {
+		if len > self.bytes.len() {
+			return Err(Error::MalformedModel);
+		}
+		let (taken, rest) = self.bytes.split_at(len);
+		self.bytes = rest;
+		Ok(taken)
+	}
+
+	fn varint(&mut self) -> Result<u64> {
+		let mut value = 0;
+		for shift in (0..64).step_by(7) {
+			let byte = self.take(1)?[0];
+			value |= u64::from(byte & 0x7f) << shift;
+			if byte & 0x80 == 0 {
+				return Ok(value);
+			}
+		}
+		Err(Error::MalformedModel)
+	}
+
+	/// Reads the next field of the message, returning its number & value.
+	fn field(&mut self) -> Result<Option<(u64, Field<'b>)>> {
+		if self.bytes.is_empty() {
+			return Ok(None);
+		}
+
+		let key = self.varint()?;
+		let value = match key & 0x7 {
+			0 => Field::Varint(self.varint()?),
+			1 => {
+				self.take(8)?;
+				Field::Fixed
+			}
+			2 => {
+				let len = usize::try_from(self.varint()?).map_err(|_| Error::MalformedModel)?;
+				Field::Bytes(self.take(len)?)
+			}
+			5 => {
+				self.take(4)?;
+				Field::Fixed
+			}
+			// groups are deprecated and not used by ONNX
+			_ => return Err(Error::MalformedModel)
+		};
+		Ok(Some((key >> 3, value)))
+	}
+}
+
+fn parse_string(bytes: &[u8]) -> Result<String> {
+	str::from_utf8(bytes).map(str::to_owned).map_err(|_| Error::MalformedModel)
+}
+
+/// Parses a `TensorProto`, returning `None` if its data is not stored externally.
+fn parse_tensor(bytes: &[u8]) -> Result<Option<ExternalTensor>> {
+	let mut tensor = ExternalTensor::default();
+	let mut is_external = false;
+
+	let mut reader = Reader::new(bytes);
+	while let Some((field, value)) = reader.field()? {
+		match (field, value) {
+			(1, Field::Varint(dim)) => tensor.dims.push(dim as i64),
+			// packed `dims`
+			(1, Field::Bytes(dims)) => {
+				let mut dims = Reader::new(dims);
+				while !dims.bytes.is_empty() {
+					tensor.dims.push(dims.varint()? as i64);
+				}
+			}
+			(2, Field::Varint(data_type)) => tensor.data_type = data_type as i32,
+			(8, Field::Bytes(name)) => tensor.name = parse_string(name)?,
+			(13, Field::Bytes(entry)) => {
+				let (mut key, mut value) = (String::new(), String::new());
+				let mut entry = Reader::new(entry);
+				while let Some((field, data)) = entry.field()? {
+					match (field, data) {
+						(1, Field::Bytes(data)) => key = parse_string(data)?,
+						(2, Field::Bytes(data)) => value = parse_string(data)?,
+						_ => {}
+					}
+				}
+				match key.as_str() {
+					"location" => tensor.location = value,
+					"offset" => tensor.offset = value.parse().map_err(|_| Error::MalformedModel)?,
+					"length" => tensor.length = Some(value.parse().map_err(|_| Error::MalformedModel)?),
+					_ => {}
+				}
+			}
+			// `data_location` is `EXTERNAL`
+			(14, Field::Varint(1)) => is_external = true,
+			_ => {}
+		}
+	}
+
+	Ok(is_external.then_some(tensor))
+}
+
+/// Finds the initializers of the main graph of an ONNX model which are stored as external data.
+pub(crate) fn external_tensors(model_bytes: &[u8]) -> Result<Vec<ExternalTensor>> {
+	let mut tensors = Vec::new();
+	let mut model = Reader::new(model_bytes);
+	while let Some((field, value)) = model.field()? {
+		// `ModelProto.graph`
+		if let (7, Field::Bytes(graph)) = (field, value) {
+			let mut graph = Reader::new(graph);
+			while let Some((field, value)) = graph.field()? {
+				// `GraphProto.initializer`
+				if let (5, Field::Bytes(tensor)) = (field, value) {
+					tensors.extend(parse_tensor(tensor)?);
+				}
+			}
+		}
+	}
+	Ok(tensors)
+}
+
+/// Maps a `TensorProto.DataType` to its element type. Strings cannot be stored as external data.
+fn element_type(data_type: i32) -> Option<TensorElementDataType> {
+	Some(match data_type {
+		1 => TensorElementDataType::Float32,
+		2 => TensorElementDataType::Uint8,
+		3 => TensorElementDataType::Int8,
+		4 => TensorElementDataType::Uint16,
+		5 => TensorElementDataType::Int16,
+		6 => TensorElementDataType::Int32,
+		7 => TensorElementDataType::Int64,
+		9 => TensorElementDataType::Bool,
+		#[cfg(feature = "half")]
+		10 => TensorElementDataType::Float16,
+		11 => TensorElementDataType::Float64,
+		12 => TensorElementDataType::Uint32,
+		13 => TensorElementDataType::Uint64,
+		#[cfg(feature = "half")]
+		16 => TensorElementDataType::Bfloat16,
+		_ => return None
+	})
+}
+
+/// Creates [`Value`]s referencing the data of each external initializer of the model in `external_data`, returning
+/// the names of the initializers along with their values. Each value keeps its buffer alive.
+pub(crate) fn load_external_initializers<B>(model_bytes: &[u8], external_data: HashMap<String, B>) -> Result<(Vec<String>, Vec<Value>)>
+where
+	B: AsRef<[u8]> + Send + Sync + 'static
+{
+	let buffers: HashMap<String, Arc<B>> = external_data.into_iter().map(|(location, buffer)| (location, Arc::new(buffer))).collect();
+
+	let mut names = Vec::new();
+	let mut values = Vec::new();
+	for tensor in external_tensors(model_bytes)? {
+		let buffer = buffers
+			.get(&tensor.location)
+			.ok_or_else(|| Error::MissingExternalData(tensor.location.clone()))?;
+		let (ty, element_size) =
+			element_type(tensor.data_type)
+				.and_then(|ty| Some((ty, ty.byte_size()?)))
+				.ok_or_else(|| Error::UnsupportedExternalDataType {
+					name: tensor.name.clone(),
+					data_type: tensor.data_type
+				})?;
+
+		let len = tensor
+			.dims
+			.iter()
+			.try_fold(element_size, |len, &dim| len.checked_mul(usize::try_from(dim).ok()?));
+		let len = len.ok_or_else(|| Error::ExternalDataOutOfBounds(tensor.name.clone()))?;
+		if let Some(length) = tensor.length {
+			if length != len {
+				return Err(Error::ExternalDataLengthMismatch {
+					name: tensor.name,
+					expected: len,
+					actual: length
+				});
+			}
+		}
+
+		let data = (*buffer)
+			.as_ref()
+			.as_ref()
+			.get(tensor.offset..)
+			.and_then(|data| data.get(..len))
+			.ok_or_else(|| Error::ExternalDataOutOfBounds(tensor.name.clone()))?;
+		let value = if data.as_ptr().align_offset(element_size) == 0 {
+			// SAFETY: `data` is borrowed from `buffer`, which the value keeps alive & is never mutated
+			unsafe { Value::from_raw_tensor_data(data.as_ptr(), len, &tensor.dims, ty, Box::new(Arc::clone(buffer)))? }
+		} else {
+			// the offset does not align the data for its element type, so copy it into an allocation aligned for any
+			// element type
+			let mut aligned = vec![0_u64; (len + 7) / 8];
+			unsafe { ptr::copy_nonoverlapping(data.as_ptr(), aligned.as_mut_ptr().cast::<u8>(), len) };
+			// SAFETY: the value takes ownership of `aligned`, whose heap allocation does not move
+			unsafe { Value::from_raw_tensor_data(aligned.as_ptr().cast(), len, &tensor.dims, ty, Box::new(aligned))? }
+		};
+		names.push(tensor.name);
+		values.push(value);
+	}
+	Ok((names, values))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_external_tensors() -> Result<()> {
+		let model = include_bytes!("../../tests/data/external_data.onnx");
+		assert_eq!(
+			external_tensors(model)?,
+			vec![ExternalTensor {
+				name: "w".to_string(),
+				data_type: 1,
+				dims: vec![3],
+				location: "external_data.bin".to_string(),
+				offset: 8,
+				length: Some(12)
+			}]
+		);
+
+		assert!(matches!(external_tensors(&model[..model.len() - 1]), Err(Error::MalformedModel)));
+		Ok(())
+	}
+}
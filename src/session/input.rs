@@ -1,19 +1,32 @@
-use std::collections::HashMap;
+use std::{borrow::Cow, collections::HashMap};
 
-use crate::Value;
+use super::Session;
+use crate::{Error, Result, Value, ValueType};
 
 pub enum SessionInputs<'i, const N: usize = 0> {
-	ValueMap(HashMap<&'static str, Value>),
+	ValueMap(HashMap<Cow<'i, str>, Value>),
 	ValueSlice(&'i [Value]),
 	ValueArray([Value; N])
 }
 
-impl<'i> From<HashMap<&'static str, Value>> for SessionInputs<'i> {
-	fn from(val: HashMap<&'static str, Value>) -> Self {
+impl<'i> From<HashMap<Cow<'i, str>, Value>> for SessionInputs<'i> {
+	fn from(val: HashMap<Cow<'i, str>, Value>) -> Self {
 		SessionInputs::ValueMap(val)
 	}
 }
 
+impl<'i> From<HashMap<&'i str, Value>> for SessionInputs<'i> {
+	fn from(val: HashMap<&'i str, Value>) -> Self {
+		SessionInputs::ValueMap(val.into_iter().map(|(k, v)| (Cow::Borrowed(k), v)).collect())
+	}
+}
+
+impl<'i> From<HashMap<String, Value>> for SessionInputs<'i> {
+	fn from(val: HashMap<String, Value>) -> Self {
+		SessionInputs::ValueMap(val.into_iter().map(|(k, v)| (Cow::Owned(k), v)).collect())
+	}
+}
+
 impl<'i> From<&'i [Value]> for SessionInputs<'i> {
 	fn from(val: &'i [Value]) -> Self {
 		SessionInputs::ValueSlice(val)
@@ -26,6 +39,101 @@ impl<'i, const N: usize> From<[Value; N]> for SessionInputs<'i, N> {
 	}
 }
 
+/// Validates the names, element types, and static dimensions of `values` against the session's inputs & overridable
+/// initializers, so that mismatches are reported as structured errors instead of an opaque error from ONNX Runtime.
+///
+/// Sequences & maps are checked against the model's element types, but not against the dimensions of the tensors they
+/// contain. Optional inputs & overridable initializers may be omitted.
+pub(crate) fn validate_inputs(session: &Session, names: &[&str], values: &[Value]) -> Result<()> {
+	if names.len() != values.len() {
+		return Err(Error::InputCountMismatch {
+			expected: names.len(),
+			actual: values.len()
+		});
+	}
+
+	for (name, value) in names.iter().zip(values) {
+		let input = session
+			.inputs
+			.iter()
+			.chain(&session.overridable_initializers)
+			.find(|input| input.name == *name)
+			.ok_or_else(|| Error::UnknownInput(name.to_string()))?;
+
+		// a value provided for an optional input must match the contained type
+		let expected = match &input.input_type {
+			ValueType::Optional(contained) => contained.as_ref(),
+			ty => ty
+		};
+		if let ValueType::Tensor { ty, dimensions, .. } = expected {
+			if !value.is_tensor()? {
+				return Err(Error::InputNotTensor(name.to_string()));
+			}
+
+			let actual_ty = value.dtype()?;
+			if actual_ty != *ty {
+				return Err(Error::InputDataTypeMismatch {
+					name: name.to_string(),
+					expected: *ty,
+					actual: actual_ty
+				});
+			}
+
+			let shape = value.shape()?;
+			// negative dimensions are dynamic & can be of any size
+			if shape.len() != dimensions.len()
+				|| dimensions
+					.iter()
+					.zip(&shape)
+					.any(|(expected, actual)| *expected >= 0 && expected != actual)
+			{
+				return Err(Error::InputShapeMismatch {
+					name: name.to_string(),
+					expected: dimensions.clone(),
+					actual: shape
+				});
+			}
+		} else {
+			let actual = value.value_type()?;
+			if !same_element_types(expected, &actual) {
+				return Err(Error::InputTypeMismatch {
+					name: name.to_string(),
+					expected: Box::new(expected.clone()),
+					actual: Box::new(actual)
+				});
+			}
+		}
+	}
+
+	if let Some(missing) = session
+		.inputs
+		.iter()
+		.filter(|input| !matches!(input.input_type, ValueType::Optional(_)))
+		.filter(|input| !session.overridable_initializers.iter().any(|initializer| initializer.name == input.name))
+		.find(|input| !names.contains(&input.name.as_str()))
+	{
+		return Err(Error::MissingInput(missing.name.clone()));
+	}
+
+	Ok(())
+}
+
+/// Compares two types by their structure & element types, ignoring tensor dimensions, which ONNX Runtime does not
+/// report for tensors contained in sequences & maps.
+fn same_element_types(expected: &ValueType, actual: &ValueType) -> bool {
+	match (expected, actual) {
+		(ValueType::Tensor { ty: expected, .. }, ValueType::Tensor { ty: actual, .. })
+		| (ValueType::SparseTensor { ty: expected, .. }, ValueType::SparseTensor { ty: actual, .. }) => expected == actual,
+		(ValueType::Sequence(expected), ValueType::Sequence(actual)) | (ValueType::Optional(expected), ValueType::Optional(actual)) => {
+			same_element_types(expected, actual)
+		}
+		(ValueType::Map { key: expected_key, value: expected }, ValueType::Map { key: actual_key, value: actual }) => {
+			expected_key == actual_key && same_element_types(expected, actual)
+		}
+		_ => false
+	}
+}
+
 /// Construct the inputs to a session from an array or map of values.
 ///
 /// The result of this macro is an `Result<SessionInputs, OrtError>`, so make sure you `?` on the result.
@@ -63,6 +171,22 @@ impl<'i, const N: usize> From<[Value; N]> for SessionInputs<'i, N> {
 /// # Ok(())
 /// # }
 /// ```
+///
+/// Input names can be any expression implementing `AsRef<str>`, so names determined at runtime can be used too:
+///
+/// ```no_run
+/// # use std::{error::Error, sync::Arc};
+/// # use ndarray::Array1;
+/// # use ort::{GraphOptimizationLevel, Session};
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let mut session = Session::builder()?.with_model_from_file("model.onnx")?;
+/// let input_name = session.inputs[0].name.clone();
+/// let _ = session.run(ort::inputs! {
+/// 	input_name => Array1::from_vec(vec![1, 2, 3, 4, 5])
+/// }?);
+/// # Ok(())
+/// # }
+/// ```
 #[macro_export]
 macro_rules! inputs {
 	($($v:expr),+ $(,)?) => (
@@ -71,8 +195,10 @@ macro_rules! inputs {
 		})()
 	);
 	($($n:expr => $v:expr),+ $(,)?) => {{
-		[$(::std::convert::TryInto::<$crate::Value>::try_into($v).map_err($crate::Error::from).map(|v| ($n, v)),)+]
+		[$(::std::convert::TryInto::<$crate::Value>::try_into($v)
+			.map_err($crate::Error::from)
+			.map(|v| (::std::string::ToString::to_string(::std::convert::AsRef::<str>::as_ref(&$n)), v)),)+]
 			.into_iter()
-			.collect::<$crate::Result<::std::collections::HashMap::<_, $crate::Value>>>()
+			.collect::<$crate::Result<::std::collections::HashMap::<::std::string::String, $crate::Value>>>()
 	}};
 }
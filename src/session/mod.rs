@@ -5,6 +5,9 @@ use std::os::unix::ffi::OsStrExt;
 #[cfg(target_family = "windows")]
 use std::os::windows::ffi::OsStrExt;
 use std::{
+	any::Any,
+	borrow::Cow,
+	collections::HashMap,
 	ffi::CString,
 	fmt,
 	marker::PhantomData,
@@ -17,6 +20,8 @@ use std::{
 #[cfg(feature = "fetch-models")]
 use std::{path::PathBuf, time::Duration};
 
+#[cfg(feature = "custom-ops")]
+use super::operator::OperatorDomain;
 use super::{
 	api, char_p_to_string,
 	environment::get_environment,
@@ -27,15 +32,34 @@ use super::{
 	memory::Allocator,
 	metadata::ModelMetadata,
 	ortsys,
+	tensor::TensorElementDataType,
 	value::{Value, ValueType},
 	AllocatorType, GraphOptimizationLevel, MemType
 };
 #[cfg(feature = "fetch-models")]
 use super::{download::ModelUrl, error::FetchModelError};
 
+pub(crate) mod batch;
+pub(crate) mod config;
+pub(crate) mod external_data;
 pub(crate) mod input;
 pub(crate) mod output;
-pub use self::{input::SessionInputs, output::SessionOutputs};
+pub(crate) mod pool;
+pub(crate) mod prepacked_weights;
+pub(crate) mod run_async;
+pub(crate) mod run_options;
+pub(crate) mod typed;
+pub use self::{
+	batch::{BatchRunner, BatchRunnerBuilder},
+	config::SessionConfig,
+	input::SessionInputs,
+	output::SessionOutputs,
+	pool::{PooledSession, SessionPool},
+	prepacked_weights::PrepackedWeights,
+	run_async::InferenceFut,
+	run_options::{OutputSelector, RunHandle, RunOptions},
+	typed::{FieldType, Inputs, Outputs, TypedField, TypedSession}
+};
 
 /// Type used to create a session using the _builder pattern_. Once created with [`Session::builder`], you can use the
 /// different methods to configure the session.
@@ -62,8 +86,13 @@ pub struct SessionBuilder {
 	allocator: AllocatorType,
 	memory_type: MemType,
 	#[cfg(feature = "custom-ops")]
-	custom_runtime_handles: Vec<*mut std::os::raw::c_void>,
-	execution_providers: Vec<ExecutionProviderDispatch>
+	custom_runtime_handles: Vec<Arc<LibraryHandle>>,
+	#[cfg(feature = "custom-ops")]
+	operator_domains: Vec<Arc<OperatorDomain>>,
+	pub(crate) execution_providers: Vec<ExecutionProviderDispatch>,
+	prepacked_weights: Option<PrepackedWeights>,
+	initializers: Vec<Arc<Value>>,
+	validate_inputs: bool
 }
 
 impl fmt::Debug for SessionBuilder {
@@ -86,7 +115,12 @@ impl Clone for SessionBuilder {
 			memory_type: self.memory_type,
 			#[cfg(feature = "custom-ops")]
 			custom_runtime_handles: self.custom_runtime_handles.clone(),
-			execution_providers: self.execution_providers.clone()
+			#[cfg(feature = "custom-ops")]
+			operator_domains: self.operator_domains.clone(),
+			execution_providers: self.execution_providers.clone(),
+			prepacked_weights: self.prepacked_weights.clone(),
+			initializers: self.initializers.clone(),
+			validate_inputs: self.validate_inputs
 		}
 	}
 }
@@ -94,11 +128,6 @@ impl Clone for SessionBuilder {
 impl Drop for SessionBuilder {
 	#[tracing::instrument]
 	fn drop(&mut self) {
-		#[cfg(feature = "custom-ops")]
-		for &handle in self.custom_runtime_handles.iter() {
-			close_lib_handle(handle);
-		}
-
 		if !self.session_options_ptr.is_null() {
 			ortsys![unsafe ReleaseSessionOptions(self.session_options_ptr)];
 		}
@@ -117,7 +146,12 @@ impl SessionBuilder {
 			memory_type: MemType::Default,
 			#[cfg(feature = "custom-ops")]
 			custom_runtime_handles: Vec::new(),
-			execution_providers: Vec::new()
+			#[cfg(feature = "custom-ops")]
+			operator_domains: Vec::new(),
+			execution_providers: Vec::new(),
+			prepacked_weights: None,
+			initializers: Vec::new(),
+			validate_inputs: true
 		})
 	}
 
@@ -223,6 +257,23 @@ impl SessionBuilder {
 		Ok(self)
 	}
 
+	/// Overrides all free dimensions with the given [denotation](https://github.com/onnx/onnx/blob/main/docs/DimensionDenotation.md)
+	/// (e.g. `DATA_BATCH`) with a fixed value. Fixing dimensions allows ONNX Runtime to apply optimizations that
+	/// require static shapes.
+	pub fn with_dimension_override<S: AsRef<str>>(self, denotation: S, value: i64) -> Result<Self> {
+		let denotation = CString::new(denotation.as_ref())?;
+		ortsys![unsafe AddFreeDimensionOverride(self.session_options_ptr, denotation.as_ptr(), value) -> Error::CreateSessionOptions];
+		Ok(self)
+	}
+
+	/// Overrides all free dimensions with the given symbolic name (e.g. `batch` or `sequence`) with a fixed value.
+	/// Fixing dimensions allows ONNX Runtime to apply optimizations that require static shapes.
+	pub fn with_dimension_override_by_name<S: AsRef<str>>(self, name: S, value: i64) -> Result<Self> {
+		let name = CString::new(name.as_ref())?;
+		ortsys![unsafe AddFreeDimensionOverrideByName(self.session_options_ptr, name.as_ptr(), value) -> Error::CreateSessionOptions];
+		Ok(self)
+	}
+
 	/// Enables profiling. Profile information will be writen to `profiling_file` after profiling completes.
 	/// See [`Session::end_profiling`].
 	#[cfg(feature = "profiling")]
@@ -246,6 +297,23 @@ impl SessionBuilder {
 		Ok(self)
 	}
 
+	/// Enables/disables validation of inputs against the model's signature before each run. Defaults to `true`.
+	///
+	/// When enabled, mismatched input names, element types, and static dimensions are reported as structured errors
+	/// like [`Error::InputShapeMismatch`] instead of an opaque [`Error::SessionRun`]. Disabling validation skips these
+	/// checks, which may be desirable in hot paths where the inputs are known to be correct.
+	pub fn with_input_validation(mut self, enable: bool) -> Result<Self> {
+		self.validate_inputs = enable;
+		Ok(self)
+	}
+
+	/// Shares pre-packed constant weights with other sessions created with the same [`PrepackedWeights`] container,
+	/// reducing memory usage when multiple sessions of the same model are used in one process.
+	pub fn with_prepacked_weights(mut self, weights: &PrepackedWeights) -> Result<Self> {
+		self.prepacked_weights = Some(weights.clone());
+		Ok(self)
+	}
+
 	/// Set the session's allocator. Defaults to [`AllocatorType::Device`].
 	pub fn with_allocator(mut self, allocator: AllocatorType) -> Result<Self> {
 		self.allocator = allocator;
@@ -258,6 +326,61 @@ impl SessionBuilder {
 		Ok(self)
 	}
 
+	/// Adds an initializer to the session, overriding the initializer of the same name in the model.
+	///
+	/// The value is not copied; instead, it is kept alive for as long as any session created from this builder. Wrap
+	/// the value in an [`Arc`] to share one weight buffer between several sessions:
+	///
+	/// ```no_run
+	/// # use std::sync::Arc;
+	/// # use ort::{Session, Value};
+	/// # fn main() -> ort::Result<()> {
+	/// let weights = Arc::new(Value::from_array(ndarray::Array2::<f32>::zeros((768, 768)))?);
+	/// let a = Session::builder()?
+	/// 	.with_initializer("weight", Arc::clone(&weights))?
+	/// 	.with_model_from_file("a.onnx")?;
+	/// let b = Session::builder()?.with_initializer("weight", weights)?.with_model_from_file("b.onnx")?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn with_initializer(mut self, name: impl AsRef<str>, value: impl Into<Arc<Value>>) -> Result<Self> {
+		let name = CString::new(name.as_ref())?;
+		let value = value.into();
+		ortsys![unsafe AddInitializer(self.session_options_ptr, name.as_ptr(), value.ptr()) -> Error::AddInitializer];
+		self.initializers.push(value);
+		Ok(self)
+	}
+
+	/// Provides the data of several initializers which the model stores as external data, instead of loading it from
+	/// the external data files. `names` and `values` must be of the same length, and each name must refer to an
+	/// initializer with external data.
+	///
+	/// Like [`SessionBuilder::with_initializer`], the values are not copied and are kept alive for as long as any
+	/// session created from this builder.
+	pub fn with_external_initializers<S, V>(mut self, names: impl IntoIterator<Item = S>, values: impl IntoIterator<Item = V>) -> Result<Self>
+	where
+		S: AsRef<str>,
+		V: Into<Arc<Value>>
+	{
+		let names = names.into_iter().map(|name| CString::new(name.as_ref())).collect::<Result<Vec<_>, _>>()?;
+		let values: Vec<Arc<Value>> = values.into_iter().map(Into::into).collect();
+		if names.len() != values.len() {
+			return Err(Error::InitializerCountMismatch {
+				names: names.len(),
+				values: values.len()
+			});
+		}
+
+		let name_ptrs: Vec<*const c_char> = names.iter().map(|name| name.as_ptr()).collect();
+		let value_ptrs: Vec<*const ort_sys::OrtValue> = values.iter().map(|value| value.ptr().cast_const()).collect();
+		ortsys![
+			unsafe AddExternalInitializers(self.session_options_ptr, name_ptrs.as_ptr(), value_ptrs.as_ptr(), values.len() as _)
+				-> Error::AddInitializer
+		];
+		self.initializers.extend(values);
+		Ok(self)
+	}
+
 	/// Registers a custom operator library with the given library path in the session.
 	#[cfg(feature = "custom-ops")]
 	#[cfg_attr(docsrs, doc(cfg(feature = "custom-ops")))]
@@ -279,11 +402,23 @@ impl SessionBuilder {
 			return Err(e);
 		}
 
-		self.custom_runtime_handles.push(handle);
+		self.custom_runtime_handles.push(Arc::new(LibraryHandle(handle)));
 
 		Ok(self)
 	}
 
+	/// Registers a domain of custom operators implemented in Rust with the session. See [`CustomOp`](crate::CustomOp).
+	///
+	/// The domain is kept alive for as long as any session created from this builder.
+	#[cfg(feature = "custom-ops")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "custom-ops")))]
+	pub fn with_operators(mut self, domain: impl Into<Arc<OperatorDomain>>) -> Result<Self> {
+		let domain = domain.into();
+		ortsys![unsafe AddCustomOpDomain(self.session_options_ptr, domain.ptr()) -> Error::AddOperatorDomain];
+		self.operator_domains.push(domain);
+		Ok(self)
+	}
+
 	/// Enable custom operators. See onnxruntime-extensions: https://github.com/microsoft/onnxruntime-extensions
 	#[cfg(feature = "custom-ops")]
 	#[cfg_attr(docsrs, doc(cfg(feature = "custom-ops")))]
@@ -358,6 +493,51 @@ impl SessionBuilder {
 		}
 	}
 
+	/// Saves the optimized graph to `path` when the session is created, so it can be loaded later with
+	/// [`GraphOptimizationLevel::Disable`] for faster startup. See [`GraphOptimizationLevel`] for more information on
+	/// offline mode, and [`optimize_model`] for a convenient wrapper.
+	///
+	/// The model is saved in the `.ort` format if `path` has the `.ort` extension, and in the `.onnx` format otherwise.
+	pub fn with_optimized_model_path<P: AsRef<Path>>(self, path: P) -> Result<Self> {
+		let path = path_to_ortchar(path.as_ref());
+		ortsys![unsafe SetOptimizedModelFilePath(self.session_options_ptr, path.as_ptr()) -> Error::CreateSessionOptions];
+		Ok(self)
+	}
+
+	/// Sets a well-known session configuration entry. See [`SessionConfig`] for the available entries.
+	pub fn with_config(self, config: SessionConfig) -> Result<Self> {
+		self.with_config_entry(config.key(), config.value())
+	}
+
+	/// Sets an arbitrary session configuration entry. Prefer [`SessionBuilder::with_config`] for documented entries.
+	///
+	/// See ONNX Runtime's [`onnxruntime_session_options_config_keys.h`](https://github.com/microsoft/onnxruntime/blob/v1.16.3/include/onnxruntime/core/session/onnxruntime_session_options_config_keys.h)
+	/// for a list of available keys.
+	pub fn with_config_entry<K: AsRef<str>, V: AsRef<str>>(self, key: K, value: V) -> Result<Self> {
+		let key = CString::new(key.as_ref())?;
+		let value = CString::new(value.as_ref())?;
+		ortsys![unsafe AddSessionConfigEntry(self.session_options_ptr, key.as_ptr(), value.as_ptr()) -> Error::CreateSessionOptions];
+		Ok(self)
+	}
+
+	/// Returns the value of a session configuration entry previously set with [`SessionBuilder::with_config`] or
+	/// [`SessionBuilder::with_config_entry`], or `None` if the entry has not been set.
+	pub fn config_entry<K: AsRef<str>>(&self, key: K) -> Result<Option<String>> {
+		let key = CString::new(key.as_ref())?;
+
+		let mut has_entry = 0;
+		ortsys![unsafe HasSessionConfigEntry(self.session_options_ptr, key.as_ptr(), &mut has_entry) -> Error::GetSessionConfigEntry];
+		if has_entry == 0 {
+			return Ok(None);
+		}
+
+		let mut size: ort_sys::size_t = 0;
+		ortsys![unsafe GetSessionConfigEntry(self.session_options_ptr, key.as_ptr(), ptr::null_mut(), &mut size) -> Error::GetSessionConfigEntry];
+		let mut value = vec![0 as c_char; size as usize];
+		ortsys![unsafe GetSessionConfigEntry(self.session_options_ptr, key.as_ptr(), value.as_mut_ptr(), &mut size) -> Error::GetSessionConfigEntry];
+		Ok(Some(char_p_to_string(value.as_ptr())?))
+	}
+
 	// TODO: Add all functions changing the options.
 	//       See all OrtApi methods taking a `options: *mut OrtSessionOptions`.
 
@@ -373,20 +553,7 @@ impl SessionBuilder {
 			});
 		}
 
-		// Build an OsString, then a vector of bytes to pass to C
-		let model_path = std::ffi::OsString::from(model_filepath);
-		#[cfg(target_family = "windows")]
-		let model_path: Vec<u16> = model_path
-            .encode_wide()
-            .chain(std::iter::once(0)) // Make sure we have a null terminated string
-            .collect();
-		#[cfg(not(target_family = "windows"))]
-		let model_path: Vec<std::os::raw::c_char> = model_path
-            .as_bytes()
-            .iter()
-            .chain(std::iter::once(&b'\0')) // Make sure we have a null terminated string
-            .map(|b| *b as std::os::raw::c_char)
-            .collect();
+		let model_path = path_to_ortchar(model_filepath);
 
 		let env = get_environment()?;
 		apply_execution_providers(&self, self.execution_providers.iter().chain(&env.execution_providers).cloned());
@@ -394,7 +561,16 @@ impl SessionBuilder {
 		let env_ptr = env.env_ptr.load(Ordering::Relaxed);
 
 		let mut session_ptr: *mut ort_sys::OrtSession = std::ptr::null_mut();
-		ortsys![unsafe CreateSession(env_ptr, model_path.as_ptr(), self.session_options_ptr, &mut session_ptr) -> Error::CreateSession; nonNull(session_ptr)];
+		match &self.prepacked_weights {
+			Some(prepacked_weights) => ortsys![
+				unsafe CreateSessionWithPrepackedWeightsContainer(env_ptr, model_path.as_ptr(), self.session_options_ptr, prepacked_weights.ptr(), &mut session_ptr)
+					-> Error::CreateSession;
+				nonNull(session_ptr)
+			],
+			None => {
+				ortsys![unsafe CreateSession(env_ptr, model_path.as_ptr(), self.session_options_ptr, &mut session_ptr) -> Error::CreateSession; nonNull(session_ptr)]
+			}
+		}
 
 		let allocator = Allocator::default();
 
@@ -407,35 +583,84 @@ impl SessionBuilder {
 		let outputs = (0..num_output_nodes)
 			.map(|i| dangerous::extract_output(session_ptr, allocator.ptr, i))
 			.collect::<Result<Vec<Output>>>()?;
+		let overridable_initializers = dangerous::extract_overridable_initializers(session_ptr, allocator.ptr)?;
 
 		Ok(Session {
-			inner: Arc::new(SharedSessionInner { session_ptr, allocator }),
+			inner: Arc::new(SharedSessionInner {
+				session_ptr,
+				allocator,
+				_prepacked_weights: self.prepacked_weights.clone(),
+				_initializers: self.initializers.clone(),
+				#[cfg(feature = "custom-ops")]
+				_operator_domains: self.operator_domains.clone(),
+				#[cfg(feature = "custom-ops")]
+				_custom_runtime_handles: self.custom_runtime_handles.clone(),
+				_model_data: None
+			}),
 			inputs,
-			outputs
+			outputs,
+			overridable_initializers,
+			validate_inputs: self.validate_inputs
 		})
 	}
 
-	/// Load an ONNX graph from memory and commit the session
-	/// For `.ort` models, we enable `session.use_ort_model_bytes_directly`.
+	/// Load an ONNX graph from memory and commit the session.
+	///
+	/// For `.ort` format models, the session references `model_bytes` directly for both the graph & its initializers
+	/// (via [`SessionConfig::UseOrtModelBytesDirectly`] & [`SessionConfig::UseOrtModelBytesForInitializers`]) instead
+	/// of copying them, so the returned [`InMemorySession`] borrows `model_bytes` for its lifetime. `.onnx` models are
+	/// loaded as with [`SessionBuilder::with_model_from_memory`].
+	///
 	/// For more information, check [Load ORT format model from an in-memory byte array](https://onnxruntime.ai/docs/performance/model-optimizations/ort-format-models.html#load-ort-format-model-from-an-in-memory-byte-array).
 	///
 	/// If you wish to store the model bytes and the [`InMemorySession`] in the same struct, look for crates that
 	/// facilitate creating self-referential structs, such as [`ouroboros`](https://github.com/joshua-maros/ouroboros).
+	/// Alternatively, use [`SessionBuilder::with_model_from_file_mapped`] to have the session own a memory-mapped
+	/// model.
 	pub fn with_model_from_memory_directly(self, model_bytes: &[u8]) -> Result<InMemorySession<'_>> {
-		let str_to_char = |s: &str| {
-			s.as_bytes()
-				.iter()
-				.chain(std::iter::once(&b'\0')) // Make sure we have a null terminated string
-				.map(|b| *b as std::os::raw::c_char)
-				.collect::<Vec<std::os::raw::c_char>>()
-		};
 		// Enable zero-copy deserialization for models in `.ort` format.
-		ortsys![unsafe AddSessionConfigEntry(self.session_options_ptr, str_to_char("session.use_ort_model_bytes_directly").as_ptr(), str_to_char("1").as_ptr())];
-		ortsys![unsafe AddSessionConfigEntry(self.session_options_ptr, str_to_char("session.use_ort_model_bytes_for_initializers").as_ptr(), str_to_char("1").as_ptr())];
+		let session = self
+			.with_config(SessionConfig::UseOrtModelBytesDirectly(true))?
+			.with_config(SessionConfig::UseOrtModelBytesForInitializers(true))?
+			.with_model_from_memory(model_bytes)?;
+		Ok(InMemorySession { session, phantom: PhantomData })
+	}
 
-		let session = self.with_model_from_memory(model_bytes)?;
+	/// Memory-maps an `.ort` format model file and commits the session, without copying the model's graph or
+	/// initializers into memory owned by ONNX Runtime. The session keeps the mapping alive until it is dropped.
+	///
+	/// Returns [`Error::NotOrtFormat`] if the file is not an `.ort` format model. The file must not be modified while
+	/// the session is alive.
+	#[cfg(feature = "mmap")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "mmap")))]
+	pub fn with_model_from_file_mapped<P>(self, model_filepath_ref: P) -> Result<Session>
+	where
+		P: AsRef<Path>
+	{
+		let model_filepath = model_filepath_ref.as_ref();
+		if !model_filepath.exists() {
+			return Err(Error::FileDoesNotExist {
+				filename: model_filepath.to_path_buf()
+			});
+		}
 
-		Ok(InMemorySession { session, phantom: PhantomData })
+		let file = std::fs::File::open(model_filepath).map_err(Error::MapModelFile)?;
+		// SAFETY: the file must not be modified while mapped, which is documented above
+		let model_bytes = unsafe { memmap2::Mmap::map(&file) }.map_err(Error::MapModelFile)?;
+		// `.ort` models are flatbuffers with the file identifier `ORTM`
+		if model_bytes.get(4..8) != Some(b"ORTM") {
+			return Err(Error::NotOrtFormat);
+		}
+
+		let mut session = self
+			.with_config(SessionConfig::LoadModelFormat(ModelFormat::Ort))?
+			.with_config(SessionConfig::UseOrtModelBytesDirectly(true))?
+			.with_config(SessionConfig::UseOrtModelBytesForInitializers(true))?
+			.with_model_from_memory(&model_bytes)?;
+		Arc::get_mut(&mut session.inner)
+			.expect("newly created session should not be shared")
+			._model_data = Some(Box::new(model_bytes));
+		Ok(session)
 	}
 
 	/// Load an ONNX graph from memory and commit the session.
@@ -449,10 +674,23 @@ impl SessionBuilder {
 
 		let model_data = model_bytes.as_ptr() as *const std::ffi::c_void;
 		let model_data_length = model_bytes.len();
-		ortsys![
-			unsafe CreateSessionFromArray(env_ptr, model_data, model_data_length as _, self.session_options_ptr, &mut session_ptr) -> Error::CreateSession;
-			nonNull(session_ptr)
-		];
+		match &self.prepacked_weights {
+			Some(prepacked_weights) => ortsys![
+				unsafe CreateSessionFromArrayWithPrepackedWeightsContainer(
+					env_ptr,
+					model_data,
+					model_data_length as _,
+					self.session_options_ptr,
+					prepacked_weights.ptr(),
+					&mut session_ptr
+				) -> Error::CreateSession;
+				nonNull(session_ptr)
+			],
+			None => ortsys![
+				unsafe CreateSessionFromArray(env_ptr, model_data, model_data_length as _, self.session_options_ptr, &mut session_ptr) -> Error::CreateSession;
+				nonNull(session_ptr)
+			]
+		}
 
 		let allocator = Allocator::default();
 
@@ -465,14 +703,121 @@ impl SessionBuilder {
 		let outputs = (0..num_output_nodes)
 			.map(|i| dangerous::extract_output(session_ptr, allocator.ptr, i))
 			.collect::<Result<Vec<Output>>>()?;
+		let overridable_initializers = dangerous::extract_overridable_initializers(session_ptr, allocator.ptr)?;
 
 		let session = Session {
-			inner: Arc::new(SharedSessionInner { session_ptr, allocator }),
+			inner: Arc::new(SharedSessionInner {
+				session_ptr,
+				allocator,
+				_prepacked_weights: self.prepacked_weights.clone(),
+				_initializers: self.initializers.clone(),
+				#[cfg(feature = "custom-ops")]
+				_operator_domains: self.operator_domains.clone(),
+				#[cfg(feature = "custom-ops")]
+				_custom_runtime_handles: self.custom_runtime_handles.clone(),
+				_model_data: None
+			}),
 			inputs,
-			outputs
+			outputs,
+			overridable_initializers,
+			validate_inputs: self.validate_inputs
 		};
 		Ok(session)
 	}
+
+	/// Load an ONNX graph whose initializers are stored as external data from memory and commit the session.
+	///
+	/// ONNX models larger than 2 GB store their initializers in separate files, which cannot be found when the model is
+	/// loaded from memory. `external_data` maps the location of each external data file, as written in the model (e.g.
+	/// `model.onnx.data`), to a buffer with its contents, like a `Vec<u8>` or a memory-mapped file. The buffers are not
+	/// copied, and are kept alive for as long as the session.
+	///
+	/// Only initializers of the main graph are read from `external_data`; external initializers of subgraphs are
+	/// still loaded from the file system.
+	///
+	/// ```no_run
+	/// # use std::collections::HashMap;
+	/// # use ort::Session;
+	/// # fn main() -> ort::Result<()> {
+	/// let model = std::fs::read("model.onnx").unwrap();
+	/// let weights = std::fs::read("model.onnx.data").unwrap();
+	/// let session = Session::builder()?
+	/// 	.with_model_from_memory_and_external_data(&model, HashMap::from([("model.onnx.data".to_string(), weights)]))?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn with_model_from_memory_and_external_data<B>(self, model_bytes: &[u8], external_data: HashMap<String, B>) -> Result<Session>
+	where
+		B: AsRef<[u8]> + Send + Sync + 'static
+	{
+		let (names, values) = external_data::load_external_initializers(model_bytes, external_data)?;
+		let builder = if names.is_empty() { self } else { self.with_external_initializers(names, values)? };
+		builder.with_model_from_memory(model_bytes)
+	}
+}
+
+/// Converts a path to a null-terminated string to pass to ONNX Runtime.
+pub(crate) fn path_to_ortchar(path: &Path) -> Vec<ort_sys::ortchar> {
+	// Build an OsString, then a vector of bytes to pass to C
+	let path = std::ffi::OsString::from(path);
+	#[cfg(target_family = "windows")]
+	let path: Vec<u16> = path
+		.encode_wide()
+		.chain(std::iter::once(0)) // Make sure we have a null terminated string
+		.collect();
+	#[cfg(not(target_family = "windows"))]
+	let path: Vec<std::os::raw::c_char> = path
+		.as_bytes()
+		.iter()
+		.chain(std::iter::once(&b'\0')) // Make sure we have a null terminated string
+		.map(|b| *b as std::os::raw::c_char)
+		.collect();
+	path
+}
+
+/// The format to save an optimized model in. See [`optimize_model`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModelFormat {
+	/// The standard ONNX format (`.onnx`).
+	Onnx,
+	/// The ONNX Runtime format (`.ort`), which can be loaded with a smaller binary size & without copying, see
+	/// [`SessionBuilder::with_model_from_memory_directly`].
+	Ort
+}
+
+impl ModelFormat {
+	fn as_str(&self) -> &'static str {
+		match self {
+			ModelFormat::Onnx => "ONNX",
+			ModelFormat::Ort => "ORT"
+		}
+	}
+}
+
+/// Optimizes the model at `input` with the given optimization level and saves the optimized model to `output` in the
+/// given format (offline mode; see [`GraphOptimizationLevel`]).
+///
+/// The optimized model can then be loaded with [`GraphOptimizationLevel::Disable`] for faster startup. Note that the
+/// optimized model should only be used with the same execution providers & on the same hardware it was optimized for.
+/// Execution providers can be configured via the environment (see
+/// [`EnvironmentBuilder::with_execution_providers`](crate::EnvironmentBuilder::with_execution_providers)) or by using
+/// [`SessionBuilder::with_optimized_model_path`] directly.
+///
+/// ```no_run
+/// # use ort::{GraphOptimizationLevel, ModelFormat};
+/// # fn main() -> ort::Result<()> {
+/// ort::optimize_model("model.onnx", "model.ort", GraphOptimizationLevel::Level3, ModelFormat::Ort)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn optimize_model<I: AsRef<Path>, O: AsRef<Path>>(input: I, output: O, level: GraphOptimizationLevel, format: ModelFormat) -> Result<()> {
+	// the optimized model is saved when the session is created
+	Session::builder()?
+		.with_optimization_level(level)?
+		.with_optimized_model_path(output)?
+		.with_config(SessionConfig::SaveModelFormat(format))?
+		.with_model_from_file(input)?;
+	Ok(())
 }
 
 /// Holds onto a C session and its allocator. This is wrapped in an [`Arc`] to ensure that [`Value`]s returned by the
@@ -480,7 +825,21 @@ impl SessionBuilder {
 #[derive(Debug)]
 pub struct SharedSessionInner {
 	pub(crate) session_ptr: *mut ort_sys::OrtSession,
-	allocator: Allocator
+	allocator: Allocator,
+	/// Must outlive the session, so it is released after the session is released in `Drop`.
+	_prepacked_weights: Option<PrepackedWeights>,
+	/// Initializers added via [`SessionBuilder::with_initializer`], which are referenced by the session without
+	/// copying.
+	_initializers: Vec<Arc<Value>>,
+	/// Custom operators added via [`SessionBuilder::with_operators`], which must outlive the session.
+	#[cfg(feature = "custom-ops")]
+	_operator_domains: Vec<Arc<OperatorDomain>>,
+	/// Custom operator libraries added via [`SessionBuilder::with_custom_ops_lib`], which must stay loaded until the
+	/// session is released.
+	#[cfg(feature = "custom-ops")]
+	_custom_runtime_handles: Vec<Arc<LibraryHandle>>,
+	/// Model bytes referenced directly by the session, e.g. a memory-mapped `.ort` model.
+	_model_data: Option<Box<dyn Any>>
 }
 
 unsafe impl Send for SharedSessionInner {}
@@ -505,7 +864,11 @@ pub struct Session {
 	/// Information about the ONNX's inputs as stored in loaded file
 	pub inputs: Vec<Input>,
 	/// Information about the ONNX's outputs as stored in loaded file
-	pub outputs: Vec<Output>
+	pub outputs: Vec<Output>,
+	/// Information about the model's overridable initializers; initializers which can be overridden for a single run by
+	/// passing a value of the same name alongside the inputs to [`Session::run`].
+	pub overridable_initializers: Vec<Input>,
+	validate_inputs: bool
 }
 
 /// A [`Session`] with data stored in-memory.
@@ -561,71 +924,171 @@ impl Session {
 
 	/// Run the input data through the ONNX graph, performing inference.
 	pub fn run<'s, 'i, const N: usize>(&'s self, input_values: impl Into<SessionInputs<'i, N>>) -> Result<SessionOutputs<'s>> {
-		match input_values.into() {
+		self.run_inner_with_options(input_values.into(), None, None)
+	}
+
+	/// Run the input data through the ONNX graph, performing inference with the given [`RunOptions`].
+	///
+	/// See [`RunOptions`] for more details.
+	pub fn run_with_options<'s, 'i, const N: usize>(
+		&'s self,
+		input_values: impl Into<SessionInputs<'i, N>>,
+		run_options: &RunOptions
+	) -> Result<SessionOutputs<'s>> {
+		self.run_inner_with_options(input_values.into(), Some(run_options), None)
+	}
+
+	/// Run the input data through the ONNX graph, writing the outputs named in `outputs` into the given pre-allocated
+	/// [`Value`]s instead of allocating new ones on every run.
+	///
+	/// Each value must have the correct type & shape for its output. The pre-allocated outputs are computed in addition
+	/// to the session's other outputs. The run takes ownership of the pre-allocated values and hands them back in the
+	/// returned [`SessionOutputs`], so they can be reused by removing them from the outputs. This is the same contract
+	/// as [`Session::run_with_options_and_outputs`] & [`Session::run_async_with_outputs`].
+	///
+	/// ```no_run
+	/// # use std::collections::HashMap;
+	/// # use ort::{Session, Value};
+	/// # fn main() -> ort::Result<()> {
+	/// # let session = Session::builder()?.with_model_from_file("model.onnx")?;
+	/// let mut output = Value::from_array(ndarray::Array2::<f32>::zeros((1, 1000)))?;
+	/// for _ in 0..100 {
+	/// 	let input = ndarray::Array4::<f32>::zeros((1, 3, 224, 224));
+	/// 	let mut outputs =
+	/// 		session.run_with_outputs(ort::inputs![input]?, HashMap::from([("output".to_string(), output)]))?;
+	/// 	output = outputs.remove("output").expect("pre-allocated output should be handed back");
+	/// 	let probabilities = output.extract_tensor::<f32>()?;
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn run_with_outputs<'s, 'i, const N: usize>(
+		&'s self,
+		input_values: impl Into<SessionInputs<'i, N>>,
+		outputs: HashMap<String, Value>
+	) -> Result<SessionOutputs<'s>> {
+		self.run_inner_with_options(input_values.into(), None, Some(outputs))
+	}
+
+	/// Run the input data through the ONNX graph with the given [`RunOptions`], writing the outputs named in `outputs`
+	/// into the given pre-allocated [`Value`]s.
+	///
+	/// Like [`Session::run_with_outputs`], the pre-allocated values are handed back in the returned
+	/// [`SessionOutputs`].
+	pub fn run_with_options_and_outputs<'s, 'i, const N: usize>(
+		&'s self,
+		input_values: impl Into<SessionInputs<'i, N>>,
+		outputs: HashMap<String, Value>,
+		run_options: &RunOptions
+	) -> Result<SessionOutputs<'s>> {
+		self.run_inner_with_options(input_values.into(), Some(run_options), Some(outputs))
+	}
+
+	fn run_inner_with_options<const N: usize>(
+		&self,
+		input_values: SessionInputs<'_, N>,
+		run_options: Option<&RunOptions>,
+		preallocated_outputs: Option<HashMap<String, Value>>
+	) -> Result<SessionOutputs<'_>> {
+		match input_values {
 			SessionInputs::ValueSlice(input_values) => {
-				let outputs = self.run_inner(&self.inputs.iter().map(|input| input.name.as_str()).collect::<Vec<_>>(), input_values)?;
-				Ok(outputs)
+				self.run_inner(&self.inputs.iter().map(|input| input.name.as_str()).collect::<Vec<_>>(), input_values, run_options, preallocated_outputs)
 			}
 			SessionInputs::ValueArray(input_values) => {
-				let outputs = self.run_inner(&self.inputs.iter().map(|input| input.name.as_str()).collect::<Vec<_>>(), &input_values)?;
-				Ok(outputs)
+				self.run_inner(&self.inputs.iter().map(|input| input.name.as_str()).collect::<Vec<_>>(), &input_values, run_options, preallocated_outputs)
 			}
 			SessionInputs::ValueMap(input_values) => {
-				let (input_names, values): (Vec<&'static str>, Vec<Value>) = input_values.into_iter().unzip();
-				self.run_inner(&input_names, &values)
+				let (input_names, values): (Vec<Cow<'_, str>>, Vec<Value>) = input_values.into_iter().unzip();
+				self.run_inner(&input_names.iter().map(|name| name.as_ref()).collect::<Vec<_>>(), &values, run_options, preallocated_outputs)
 			}
 		}
 	}
 
-	fn run_inner(&self, input_names: &[&str], input_values: &[Value]) -> Result<SessionOutputs<'_>> {
-		let input_names_ptr: Vec<*const c_char> = input_names
+	/// Returns the names of the outputs that should be computed by a run with the given options.
+	fn selected_outputs(&self, run_options: Option<&RunOptions>) -> Result<Vec<&str>> {
+		match run_options {
+			Some(run_options) => run_options.outputs.resolve(&self.outputs),
+			None => Ok(self.outputs.iter().map(|output| output.name.as_str()).collect())
+		}
+	}
+
+	/// Checks that each pre-allocated output exists in the session, & removes them from the outputs that ONNX Runtime
+	/// should allocate. Returns the names of the pre-allocated outputs as borrowed from the session.
+	fn exclude_preallocated_outputs<'s, 'v>(
+		&'s self,
+		selected_outputs: &mut Vec<&'s str>,
+		preallocated_names: impl Iterator<Item = &'v String>
+	) -> Result<Vec<&'s str>> {
+		preallocated_names
+			.map(|name| {
+				let output = self
+					.outputs
+					.iter()
+					.find(|output| output.name == *name)
+					.ok_or_else(|| Error::UnknownOutput(name.clone()))?;
+				selected_outputs.retain(|selected| selected != name);
+				Ok(output.name.as_str())
+			})
+			.collect()
+	}
+
+	fn run_inner(
+		&self,
+		input_names: &[&str],
+		input_values: &[Value],
+		run_options: Option<&RunOptions>,
+		preallocated_outputs: Option<HashMap<String, Value>>
+	) -> Result<SessionOutputs<'_>> {
+		if self.validate_inputs {
+			input::validate_inputs(self, input_names, input_values)?;
+		}
+
+		let mut output_names = self.selected_outputs(run_options)?;
+		let (preallocated_names, preallocated_values): (Vec<String>, Vec<Value>) = preallocated_outputs.unwrap_or_default().into_iter().unzip();
+		let preallocated_names = self.exclude_preallocated_outputs(&mut output_names, preallocated_names.iter())?;
+
+		let input_names_cstr = input_names.iter().map(|n| CString::new(*n)).collect::<Result<Vec<_>, _>>()?;
+		let input_names_ptr: Vec<*const c_char> = input_names_cstr.iter().map(|n| n.as_ptr()).collect();
+		// Pre-allocated outputs are passed to ONNX Runtime after the outputs it should allocate.
+		let output_names_cstr = output_names
 			.iter()
-			.map(|n| CString::new(*n).unwrap())
-			.map(|n| n.into_raw() as *const c_char)
-			.collect();
-		let output_names_ptr: Vec<*const c_char> = self
-			.outputs
+			.chain(&preallocated_names)
+			.map(|n| CString::new(*n))
+			.collect::<Result<Vec<_>, _>>()?;
+		let output_names_ptr: Vec<*const c_char> = output_names_cstr.iter().map(|n| n.as_ptr()).collect();
+		let mut output_tensor_ptrs: Vec<*mut ort_sys::OrtValue> = output_names
 			.iter()
-			.map(|output| CString::new(output.name.as_str()).unwrap())
-			.map(|n| n.into_raw() as *const c_char)
+			.map(|_| ptr::null_mut())
+			.chain(preallocated_values.iter().map(Value::ptr))
 			.collect();
 
-		let mut output_tensor_ptrs: Vec<*mut ort_sys::OrtValue> = vec![std::ptr::null_mut(); self.outputs.len()];
-
 		// The C API expects pointers for the arrays (pointers to C-arrays)
 		let input_ort_values: Vec<*const ort_sys::OrtValue> = input_values.iter().map(|input_array_ort| input_array_ort.ptr() as *const _).collect();
 
+		let run_options_ptr = run_options.map(|o| o.ptr() as *const _).unwrap_or_else(ptr::null);
+
 		ortsys![
 			unsafe Run(
 				self.inner.session_ptr,
-				ptr::null(),
+				run_options_ptr,
 				input_names_ptr.as_ptr(),
 				input_ort_values.as_ptr(),
 				input_ort_values.len() as _,
 				output_names_ptr.as_ptr(),
 				output_names_ptr.len() as _,
 				output_tensor_ptrs.as_mut_ptr()
-			) -> Error::SessionRun
+			) -> run_options::map_run_error(run_options, Error::SessionRun)
 		];
 
+		// ONNX Runtime wrote the pre-allocated outputs in place, so hand back the same values
 		let outputs: Vec<Value> = output_tensor_ptrs
 			.into_iter()
+			.take(output_names.len())
 			.map(|tensor_ptr| unsafe { Value::from_raw(tensor_ptr, Arc::clone(&self.inner)) })
+			.chain(preallocated_values)
 			.collect();
 
-		// Reconvert name ptrs to CString so drop impl is called and memory is freed
-		drop(
-			input_names_ptr
-				.into_iter()
-				.chain(output_names_ptr.into_iter())
-				.map(|p| {
-					assert_non_null_pointer(p, "c_char for CString")?;
-					unsafe { Ok(CString::from_raw(p as *mut c_char)) }
-				})
-				.collect::<Result<Vec<_>>>()?
-		);
-
-		Ok(SessionOutputs::new(self.outputs.iter().map(|o| o.name.as_str()), outputs))
+		Ok(SessionOutputs::new(output_names.into_iter().chain(preallocated_names), outputs))
 	}
 
 	/// Gets the session model metadata. See [`ModelMetadata`] for more info.
@@ -653,6 +1116,25 @@ impl Session {
 unsafe impl Send for Session {}
 unsafe impl Sync for Session {}
 
+/// A custom operator library loaded by `RegisterCustomOpsLibrary`. Builders cloned from one another & the sessions they
+/// create share the handle, so the library is closed exactly once, after the last of them is dropped.
+#[cfg(feature = "custom-ops")]
+#[derive(Debug)]
+struct LibraryHandle(*mut std::os::raw::c_void);
+
+// the handle is only ever passed to `dlclose`/`FreeLibrary`, which are thread-safe
+#[cfg(feature = "custom-ops")]
+unsafe impl Send for LibraryHandle {}
+#[cfg(feature = "custom-ops")]
+unsafe impl Sync for LibraryHandle {}
+
+#[cfg(feature = "custom-ops")]
+impl Drop for LibraryHandle {
+	fn drop(&mut self) {
+		close_lib_handle(self.0);
+	}
+}
+
 #[cfg(all(unix, feature = "custom-ops"))]
 fn close_lib_handle(handle: *mut std::os::raw::c_void) {
 	unsafe { libc::dlclose(handle) };
@@ -666,11 +1148,11 @@ fn close_lib_handle(handle: *mut std::os::raw::c_void) {
 /// This module contains dangerous functions working on raw pointers.
 /// Those functions are only to be used from inside the
 /// `SessionBuilder::with_model_from_file()` method.
-mod dangerous {
+pub(crate) mod dangerous {
 	use super::*;
 	use crate::ortfree;
 
-	unsafe fn extract_data_type_from_tensor_info(info_ptr: *const ort_sys::OrtTensorTypeAndShapeInfo) -> Result<ValueType> {
+	unsafe fn extract_tensor_info(info_ptr: *const ort_sys::OrtTensorTypeAndShapeInfo) -> Result<(TensorElementDataType, Vec<i64>, Vec<Option<String>>)> {
 		let mut type_sys = ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED;
 		ortsys![GetTensorElementType(info_ptr, &mut type_sys) -> Error::GetTensorElementType];
 		assert_ne!(type_sys, ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED);
@@ -681,54 +1163,97 @@ mod dangerous {
 		let mut node_dims: Vec<i64> = vec![0; num_dims as _];
 		ortsys![GetDimensions(info_ptr, node_dims.as_mut_ptr(), num_dims as _) -> Error::GetDimensions];
 
-		Ok(ValueType::Tensor {
-			ty: type_sys.into(),
-			dimensions: node_dims
-		})
+		// symbolic names are owned by the type info; unnamed dimensions have an empty name
+		let mut symbols: Vec<*const c_char> = vec![ptr::null(); num_dims as _];
+		ortsys![GetSymbolicDimensions(info_ptr, symbols.as_mut_ptr(), num_dims as _) -> Error::GetSymbolicDimensions];
+
+		let dimension_symbols = node_dims
+			.iter()
+			.zip(symbols)
+			.map(|(&size, symbol)| {
+				if size >= 0 || symbol.is_null() {
+					return Ok(None);
+				}
+				let symbol = char_p_to_string(symbol)?;
+				Ok(if symbol.is_empty() { None } else { Some(symbol) })
+			})
+			.collect::<Result<Vec<_>>>()?;
+
+		Ok((type_sys.into(), node_dims, dimension_symbols))
 	}
 
 	unsafe fn extract_data_type_from_sequence_info(info_ptr: *const ort_sys::OrtSequenceTypeInfo) -> Result<ValueType> {
 		let mut element_type_info: *mut ort_sys::OrtTypeInfo = std::ptr::null_mut();
-		ortsys![GetSequenceElementType(info_ptr, &mut element_type_info) -> Error::GetSequenceElementType];
+		ortsys![GetSequenceElementType(info_ptr, &mut element_type_info) -> Error::GetSequenceElementType; nonNull(element_type_info)];
+		let ty = unsafe { extract_owned_data_type(element_type_info)? };
+		Ok(ValueType::Sequence(Box::new(ty)))
+	}
+
+	unsafe fn extract_data_type_from_map_info(info_ptr: *const ort_sys::OrtMapTypeInfo) -> Result<ValueType> {
+		let mut key_type_sys = ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED;
+		ortsys![GetMapKeyType(info_ptr, &mut key_type_sys) -> Error::GetMapKeyType];
+		assert_ne!(key_type_sys, ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED);
+
+		let mut value_type_info: *mut ort_sys::OrtTypeInfo = std::ptr::null_mut();
+		ortsys![GetMapValueType(info_ptr, &mut value_type_info) -> Error::GetMapValueType; nonNull(value_type_info)];
+		let value = unsafe { extract_owned_data_type(value_type_info)? };
 
+		Ok(ValueType::Map {
+			key: key_type_sys.into(),
+			value: Box::new(value)
+		})
+	}
+
+	unsafe fn extract_data_type_from_optional_info(info_ptr: *const ort_sys::OrtOptionalTypeInfo) -> Result<ValueType> {
+		let mut contained_type_info: *mut ort_sys::OrtTypeInfo = std::ptr::null_mut();
+		ortsys![GetOptionalContainedTypeInfo(info_ptr, &mut contained_type_info) -> Error::GetOptionalContainedTypeInfo; nonNull(contained_type_info)];
+		let ty = unsafe { extract_owned_data_type(contained_type_info)? };
+		Ok(ValueType::Optional(Box::new(ty)))
+	}
+
+	/// Extracts the [`ValueType`] described by a type info, recursing into the element types of sequences, maps, and
+	/// optionals.
+	unsafe fn extract_data_type(typeinfo_ptr: *const ort_sys::OrtTypeInfo) -> Result<ValueType> {
 		let mut ty: ort_sys::ONNXType = ort_sys::ONNXType::ONNX_TYPE_UNKNOWN;
-		let status = ortsys![unsafe GetOnnxTypeFromTypeInfo(element_type_info, &mut ty)];
+		let status = ortsys![unsafe GetOnnxTypeFromTypeInfo(typeinfo_ptr, &mut ty)];
 		status_to_result(status).map_err(Error::GetOnnxTypeFromTypeInfo)?;
-
 		match ty {
 			ort_sys::ONNXType::ONNX_TYPE_TENSOR => {
 				let mut info_ptr: *const ort_sys::OrtTensorTypeAndShapeInfo = std::ptr::null_mut();
-				ortsys![unsafe CastTypeInfoToTensorInfo(element_type_info, &mut info_ptr) -> Error::CastTypeInfoToTensorInfo; nonNull(info_ptr)];
-				let ty = unsafe { extract_data_type_from_tensor_info(info_ptr)? };
-				Ok(ValueType::Sequence(Box::new(ty)))
+				ortsys![unsafe CastTypeInfoToTensorInfo(typeinfo_ptr, &mut info_ptr) -> Error::CastTypeInfoToTensorInfo; nonNull(info_ptr)];
+				let (ty, dimensions, dimension_symbols) = unsafe { extract_tensor_info(info_ptr)? };
+				Ok(ValueType::Tensor { ty, dimensions, dimension_symbols })
+			}
+			ort_sys::ONNXType::ONNX_TYPE_SPARSETENSOR => {
+				let mut info_ptr: *const ort_sys::OrtTensorTypeAndShapeInfo = std::ptr::null_mut();
+				ortsys![unsafe CastTypeInfoToTensorInfo(typeinfo_ptr, &mut info_ptr) -> Error::CastTypeInfoToTensorInfo; nonNull(info_ptr)];
+				let (ty, dimensions, dimension_symbols) = unsafe { extract_tensor_info(info_ptr)? };
+				Ok(ValueType::SparseTensor { ty, dimensions, dimension_symbols })
+			}
+			ort_sys::ONNXType::ONNX_TYPE_SEQUENCE => {
+				let mut info_ptr: *const ort_sys::OrtSequenceTypeInfo = std::ptr::null_mut();
+				ortsys![unsafe CastTypeInfoToSequenceTypeInfo(typeinfo_ptr, &mut info_ptr) -> Error::CastTypeInfoToSequenceTypeInfo; nonNull(info_ptr)];
+				unsafe { extract_data_type_from_sequence_info(info_ptr) }
 			}
 			ort_sys::ONNXType::ONNX_TYPE_MAP => {
 				let mut info_ptr: *const ort_sys::OrtMapTypeInfo = std::ptr::null_mut();
-				ortsys![unsafe CastTypeInfoToMapTypeInfo(element_type_info, &mut info_ptr) -> Error::CastTypeInfoToMapTypeInfo; nonNull(info_ptr)];
-				let ty = unsafe { extract_data_type_from_map_info(info_ptr)? };
-				Ok(ValueType::Sequence(Box::new(ty)))
+				ortsys![unsafe CastTypeInfoToMapTypeInfo(typeinfo_ptr, &mut info_ptr) -> Error::CastTypeInfoToMapTypeInfo; nonNull(info_ptr)];
+				unsafe { extract_data_type_from_map_info(info_ptr) }
+			}
+			ort_sys::ONNXType::ONNX_TYPE_OPTIONAL => {
+				let mut info_ptr: *const ort_sys::OrtOptionalTypeInfo = std::ptr::null_mut();
+				ortsys![unsafe CastTypeInfoToOptionalTypeInfo(typeinfo_ptr, &mut info_ptr) -> Error::CastTypeInfoToOptionalTypeInfo; nonNull(info_ptr)];
+				unsafe { extract_data_type_from_optional_info(info_ptr) }
 			}
-			_ => unreachable!()
+			ty => Err(Error::UnsupportedOnnxType(ty))
 		}
 	}
 
-	unsafe fn extract_data_type_from_map_info(info_ptr: *const ort_sys::OrtMapTypeInfo) -> Result<ValueType> {
-		let mut key_type_sys = ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED;
-		ortsys![GetMapKeyType(info_ptr, &mut key_type_sys) -> Error::GetMapKeyType];
-		assert_ne!(key_type_sys, ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED);
-
-		let mut value_type_info: *mut ort_sys::OrtTypeInfo = std::ptr::null_mut();
-		ortsys![GetMapValueType(info_ptr, &mut value_type_info) -> Error::GetMapValueType];
-		let mut value_info_ptr: *const ort_sys::OrtTensorTypeAndShapeInfo = std::ptr::null_mut();
-		ortsys![unsafe CastTypeInfoToTensorInfo(value_type_info, &mut value_info_ptr) -> Error::CastTypeInfoToTensorInfo; nonNull(value_info_ptr)];
-		let mut value_type_sys = ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED;
-		ortsys![GetTensorElementType(value_info_ptr, &mut value_type_sys) -> Error::GetTensorElementType];
-		assert_ne!(value_type_sys, ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED);
-
-		Ok(ValueType::Map {
-			key: key_type_sys.into(),
-			value: value_type_sys.into()
-		})
+	/// Like [`extract_data_type`], but releases the type info afterwards.
+	pub(crate) unsafe fn extract_owned_data_type(typeinfo_ptr: *mut ort_sys::OrtTypeInfo) -> Result<ValueType> {
+		let ty = unsafe { extract_data_type(typeinfo_ptr) };
+		ortsys![unsafe ReleaseTypeInfo(typeinfo_ptr)];
+		ty
 	}
 
 	pub(super) fn extract_inputs_count(session_ptr: *mut ort_sys::OrtSession) -> Result<usize> {
@@ -811,6 +1336,22 @@ mod dangerous {
 		Ok(Output { name: output_name, output_type })
 	}
 
+	pub(super) fn extract_overridable_initializers(session_ptr: *mut ort_sys::OrtSession, allocator_ptr: *mut ort_sys::OrtAllocator) -> Result<Vec<Input>> {
+		// unlike inputs & outputs, a model may have no overridable initializers
+		let mut num_initializers: ort_sys::size_t = 0;
+		ortsys![unsafe SessionGetOverridableInitializerCount(session_ptr, &mut num_initializers) -> Error::GetInOutCount];
+
+		(0..num_initializers)
+			.map(|i| {
+				let f = api().SessionGetOverridableInitializerName.unwrap();
+				let name = extract_io_name(f, session_ptr, allocator_ptr, i)?;
+				let f = api().SessionGetOverridableInitializerTypeInfo.unwrap();
+				let input_type = extract_io(f, session_ptr, i)?;
+				Ok(Input { name, input_type })
+			})
+			.collect()
+	}
+
 	fn extract_io(
 		f: extern_system_fn! { unsafe fn(
 			*const ort_sys::OrtSession,
@@ -826,29 +1367,6 @@ mod dangerous {
 		status_to_result(status).map_err(Error::GetTypeInfo)?;
 		assert_non_null_pointer(typeinfo_ptr, "TypeInfo")?;
 
-		let mut ty: ort_sys::ONNXType = ort_sys::ONNXType::ONNX_TYPE_UNKNOWN;
-		let status = ortsys![unsafe GetOnnxTypeFromTypeInfo(typeinfo_ptr, &mut ty)];
-		status_to_result(status).map_err(Error::GetOnnxTypeFromTypeInfo)?;
-		let io_type = match ty {
-			ort_sys::ONNXType::ONNX_TYPE_TENSOR | ort_sys::ONNXType::ONNX_TYPE_SPARSETENSOR => {
-				let mut info_ptr: *const ort_sys::OrtTensorTypeAndShapeInfo = std::ptr::null_mut();
-				ortsys![unsafe CastTypeInfoToTensorInfo(typeinfo_ptr, &mut info_ptr) -> Error::CastTypeInfoToTensorInfo; nonNull(info_ptr)];
-				unsafe { extract_data_type_from_tensor_info(info_ptr)? }
-			}
-			ort_sys::ONNXType::ONNX_TYPE_SEQUENCE => {
-				let mut info_ptr: *const ort_sys::OrtSequenceTypeInfo = std::ptr::null_mut();
-				ortsys![unsafe CastTypeInfoToSequenceTypeInfo(typeinfo_ptr, &mut info_ptr) -> Error::CastTypeInfoToSequenceTypeInfo; nonNull(info_ptr)];
-				unsafe { extract_data_type_from_sequence_info(info_ptr)? }
-			}
-			ort_sys::ONNXType::ONNX_TYPE_MAP => {
-				let mut info_ptr: *const ort_sys::OrtMapTypeInfo = std::ptr::null_mut();
-				ortsys![unsafe CastTypeInfoToMapTypeInfo(typeinfo_ptr, &mut info_ptr) -> Error::CastTypeInfoToMapTypeInfo; nonNull(info_ptr)];
-				unsafe { extract_data_type_from_map_info(info_ptr)? }
-			}
-			_ => unreachable!()
-		};
-
-		ortsys![unsafe ReleaseTypeInfo(typeinfo_ptr)];
-		Ok(io_type)
+		unsafe { extract_owned_data_type(typeinfo_ptr) }
 	}
 }
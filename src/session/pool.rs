@@ -0,0 +1,138 @@
+use std::{
+	ops::Deref,
+	path::Path,
+	sync::{Condvar, Mutex, MutexGuard, PoisonError}
+};
+
+use super::{PrepackedWeights, Session, SessionBuilder, SessionInputs, SessionOutputs};
+use crate::{Error, Result};
+
+/// A pool of independent [`Session`]s for the same model, for use cases where a single session cannot be shared across
+/// threads efficiently, e.g. when each session has its own thread pool or CUDA graph.
+///
+/// All sessions in the pool share their pre-packed weights via [`PrepackedWeights`]. If the builder was not configured
+/// with [`SessionBuilder::with_prepacked_weights`], a new container is created for the pool.
+///
+/// Sessions are checked out with [`SessionPool::get`], which returns a [`PooledSession`] guard that returns the session
+/// to the pool when dropped. [`SessionPool::run`] checks out a session for the duration of a single run.
+///
+/// ```no_run
+/// # use ort::{Session, SessionPool};
+/// # fn main() -> ort::Result<()> {
+/// let pool = SessionPool::from_file(Session::builder()?.with_intra_threads(1)?, "model.onnx", 4)?;
+/// std::thread::scope(|s| {
+/// 	for _ in 0..8 {
+/// 		s.spawn(|| -> ort::Result<()> {
+/// 			let outputs = pool.run(ort::inputs![ndarray::Array2::<f32>::zeros((1, 128))]?)?;
+/// 			Ok(())
+/// 		});
+/// 	}
+/// });
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SessionPool {
+	sessions: Vec<Session>,
+	available: Mutex<Vec<usize>>,
+	released: Condvar
+}
+
+impl SessionPool {
+	/// Builds a pool of `size` sessions from the model at `model_filepath`, each configured by `builder`.
+	pub fn from_file<P>(builder: SessionBuilder, model_filepath: P, size: usize) -> Result<Self>
+	where
+		P: AsRef<Path>
+	{
+		Self::build(builder, size, |builder| builder.with_model_from_file(model_filepath.as_ref()))
+	}
+
+	/// Builds a pool of `size` sessions from an in-memory model, each configured by `builder`.
+	pub fn from_memory(builder: SessionBuilder, model_bytes: &[u8], size: usize) -> Result<Self> {
+		Self::build(builder, size, |builder| builder.with_model_from_memory(model_bytes))
+	}
+
+	fn build(mut builder: SessionBuilder, size: usize, commit: impl Fn(SessionBuilder) -> Result<Session>) -> Result<Self> {
+		if size == 0 {
+			return Err(Error::EmptySessionPool);
+		}
+
+		// all sessions in the pool share their pre-packed weights
+		if builder.prepacked_weights.is_none() {
+			builder.prepacked_weights = Some(PrepackedWeights::new()?);
+		}
+
+		let sessions = (0..size).map(|_| commit(builder.clone())).collect::<Result<Vec<_>>>()?;
+		Ok(Self {
+			available: Mutex::new((0..size).rev().collect()),
+			released: Condvar::new(),
+			sessions
+		})
+	}
+
+	fn lock(&self) -> MutexGuard<'_, Vec<usize>> {
+		// the lock is never held across code which could panic, so poisoning can be ignored
+		self.available.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+
+	/// Checks out a session, blocking until one is available.
+	pub fn get(&self) -> PooledSession<'_> {
+		let mut available = self.lock();
+		loop {
+			if let Some(index) = available.pop() {
+				return PooledSession { pool: self, index };
+			}
+			available = self.released.wait(available).unwrap_or_else(PoisonError::into_inner);
+		}
+	}
+
+	/// Checks out a session if one is available, without blocking.
+	pub fn try_get(&self) -> Option<PooledSession<'_>> {
+		self.lock().pop().map(|index| PooledSession { pool: self, index })
+	}
+
+	/// Run the input data through a session from the pool, blocking until a session is available.
+	///
+	/// See [`Session::run`].
+	pub fn run<'s, 'i, const N: usize>(&'s self, input_values: impl Into<SessionInputs<'i, N>>) -> Result<SessionOutputs<'s>> {
+		let session = self.get();
+		self.sessions[session.index].run(input_values)
+	}
+
+	/// Returns the number of sessions in the pool.
+	pub fn size(&self) -> usize {
+		self.sessions.len()
+	}
+
+	/// Returns the number of sessions which are currently checked out.
+	pub fn in_use(&self) -> usize {
+		self.sessions.len() - self.lock().len()
+	}
+
+	/// Returns the fraction of sessions which are currently checked out, from `0.0` (idle) to `1.0` (fully utilized).
+	pub fn utilization(&self) -> f32 {
+		self.in_use() as f32 / self.sessions.len() as f32
+	}
+}
+
+/// A [`Session`] checked out from a [`SessionPool`]. The session is returned to the pool when this guard is dropped.
+#[derive(Debug)]
+pub struct PooledSession<'p> {
+	pool: &'p SessionPool,
+	index: usize
+}
+
+impl<'p> Deref for PooledSession<'p> {
+	type Target = Session;
+
+	fn deref(&self) -> &Self::Target {
+		&self.pool.sessions[self.index]
+	}
+}
+
+impl<'p> Drop for PooledSession<'p> {
+	fn drop(&mut self) {
+		self.pool.lock().push(self.index);
+		self.pool.released.notify_one();
+	}
+}
@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use crate::{ortsys, Error, Result};
+
+#[derive(Debug)]
+struct PrepackedWeightsInner {
+	ptr: *mut ort_sys::OrtPrepackedWeightsContainer
+}
+
+unsafe impl Send for PrepackedWeightsInner {}
+unsafe impl Sync for PrepackedWeightsInner {}
+
+impl Drop for PrepackedWeightsInner {
+	#[tracing::instrument]
+	fn drop(&mut self) {
+		ortsys![unsafe ReleasePrepackedWeightsContainer(self.ptr)];
+	}
+}
+
+/// A container for weights which have been pre-packed by ONNX Runtime's kernels (e.g. into a layout better suited for
+/// matrix multiplication).
+///
+/// Sessions of the same model created with the same container via
+/// [`SessionBuilder::with_prepacked_weights`](crate::SessionBuilder::with_prepacked_weights) will share their
+/// pre-packed constant weights instead of each holding their own copy, reducing the memory footprint of running
+/// multiple replicas of a model in one process.
+///
+/// The container is kept alive by every session created with it, so it is safe to drop it once all sessions have been
+/// created.
+///
+/// ```no_run
+/// # use ort::{PrepackedWeights, Session};
+/// # fn main() -> ort::Result<()> {
+/// let weights = PrepackedWeights::new()?;
+/// let session_a = Session::builder()?.with_prepacked_weights(&weights)?.with_model_from_file("model.onnx")?;
+/// let session_b = Session::builder()?.with_prepacked_weights(&weights)?.with_model_from_file("model.onnx")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PrepackedWeights {
+	inner: Arc<PrepackedWeightsInner>
+}
+
+impl PrepackedWeights {
+	/// Creates a new, empty container.
+	pub fn new() -> Result<Self> {
+		let mut ptr: *mut ort_sys::OrtPrepackedWeightsContainer = std::ptr::null_mut();
+		ortsys![unsafe CreatePrepackedWeightsContainer(&mut ptr) -> Error::CreatePrepackedWeights; nonNull(ptr)];
+		Ok(Self {
+			inner: Arc::new(PrepackedWeightsInner { ptr })
+		})
+	}
+
+	pub(crate) fn ptr(&self) -> *mut ort_sys::OrtPrepackedWeightsContainer {
+		self.inner.ptr
+	}
+}
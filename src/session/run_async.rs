@@ -0,0 +1,361 @@
+use std::{
+	borrow::Cow,
+	collections::HashMap,
+	ffi::{c_void, CString},
+	future::Future,
+	mem::ManuallyDrop,
+	os::raw::c_char,
+	pin::Pin,
+	ptr,
+	sync::{Arc, Mutex, PoisonError},
+	task::{Context, Poll, Waker},
+	thread
+};
+
+use super::{input::validate_inputs, RunHandle, RunOptions, Session, SessionInputs, SessionOutputs, SharedSessionInner};
+use crate::{error::status_to_result, extern_system_fn, ortsys, Error, Result, Value};
+
+/// The result of a run, along with the context passed to ONNX Runtime (absent if the run failed to launch).
+type Completion = (Result<()>, Option<Box<AsyncInferenceContext>>);
+
+#[derive(Debug, Default)]
+struct InferenceFutState {
+	completion: Option<Completion>,
+	waker: Option<Waker>,
+	/// Set if the future is dropped before the run completes.
+	abandoned: bool
+}
+
+/// A [`Future`] resolving to the outputs of an asynchronous run, returned by [`Session::run_async`] and
+/// [`Session::run_async_with_options`].
+///
+/// Dropping the future before it resolves will terminate the run if it was started with [`Session::run_async`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct InferenceFut<'s> {
+	output_names: Vec<&'s str>,
+	state: Arc<Mutex<InferenceFutState>>,
+	/// Handle used to terminate the run when the future is dropped early. Only present if the run options were created
+	/// by us; we do not want to terminate options shared with other runs.
+	owned_run_handle: Option<RunHandle>,
+	received: bool
+}
+
+impl<'s> InferenceFut<'s> {
+	fn failed(error: Error) -> Self {
+		Self {
+			output_names: Vec::new(),
+			state: Arc::new(Mutex::new(InferenceFutState {
+				completion: Some((Err(error), None)),
+				..Default::default()
+			})),
+			owned_run_handle: None,
+			received: false
+		}
+	}
+}
+
+impl<'s> Future for InferenceFut<'s> {
+	type Output = Result<SessionOutputs<'s>>;
+
+	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		let this = self.get_mut();
+		let mut state = this.state.lock().expect("inference future state lock poisoned");
+		if let Some((result, ctx)) = state.completion.take() {
+			drop(state);
+			this.received = true;
+			let outputs = result.map(|()| ctx.expect("completed run should have a context").take_outputs());
+			return Poll::Ready(outputs.map(|values| SessionOutputs::new(this.output_names.iter().copied(), values)));
+		}
+
+		state.waker = Some(cx.waker().clone());
+		Poll::Pending
+	}
+}
+
+impl<'s> Drop for InferenceFut<'s> {
+	fn drop(&mut self) {
+		if self.received {
+			return;
+		}
+		if let Some(handle) = &self.owned_run_handle {
+			let _ = handle.terminate();
+		}
+
+		let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+		// Release a completed run's context here rather than in the callback; see `async_callback`.
+		let completion = state.completion.take();
+		if completion.is_none() {
+			state.abandoned = true;
+		}
+		drop(state);
+		drop(completion);
+	}
+}
+
+/// Everything that must stay alive until ONNX Runtime invokes [`async_callback`]. Ownership is passed to ONNX Runtime
+/// via `user_data` and handed back to the [`InferenceFut`] in the callback.
+#[derive(Debug)]
+struct AsyncInferenceContext {
+	/// Taken by the callback, so that the state does not own a reference to itself once the context is stored in it.
+	state: Option<Arc<Mutex<InferenceFutState>>>,
+	session_inner: Arc<SharedSessionInner>,
+	run_handle: RunHandle,
+	_input_values: Vec<Value>,
+	_input_names: Vec<CString>,
+	_output_names: Vec<CString>,
+	input_ort_values: Vec<*const ort_sys::OrtValue>,
+	input_name_ptrs: Vec<*const c_char>,
+	output_name_ptrs: Vec<*const c_char>,
+	output_value_ptrs: Vec<*mut ort_sys::OrtValue>,
+	/// Pre-allocated values for each output, which are exclusively owned by the run until it completes.
+	preallocated_outputs: Vec<Option<Value>>
+}
+
+// The pointers only reference data owned by the context itself, or values owned by ONNX Runtime which are not shared.
+unsafe impl Send for AsyncInferenceContext {}
+
+impl AsyncInferenceContext {
+	/// Returns the outputs of a successful run, handing back the pre-allocated values.
+	fn take_outputs(mut self: Box<Self>) -> Vec<Value> {
+		let session_inner = &self.session_inner;
+		self.output_value_ptrs
+			.iter()
+			.zip(self.preallocated_outputs.iter_mut())
+			.map(|(&value_ptr, preallocated)| match preallocated.take() {
+				Some(value) => value,
+				None => unsafe { Value::from_raw(value_ptr, Arc::clone(session_inner)) }
+			})
+			.collect()
+	}
+}
+
+extern_system_fn! {
+	fn async_callback(user_data: *mut c_void, _: *mut *mut ort_sys::OrtValue, _: ort_sys::size_t, status: *mut ort_sys::OrtStatus) {
+		let mut ctx = unsafe { Box::from_raw(user_data as *mut AsyncInferenceContext) };
+		let state = ctx.state.take().expect("context should hold the future's state until the callback");
+
+		let result = match status_to_result(status) {
+			Ok(()) => Ok(()),
+			Err(_) if ctx.run_handle.is_terminated() => Err(Error::RunTerminated),
+			Err(e) => Err(Error::SessionRun(e))
+		};
+
+		// The context may hold the last reference to the session (or to values from it), and a session must not be
+		// released from one of its own threads, so the context is never dropped here. If the future is still alive, it
+		// takes the context & releases it when polled or dropped; otherwise, it is released on another thread (or leaked
+		// if no thread can be spawned).
+		let mut guard = state.lock().unwrap_or_else(PoisonError::into_inner);
+		if guard.abandoned {
+			drop(guard);
+			let ctx = ManuallyDrop::new(ctx);
+			let _ = thread::Builder::new()
+				.name("ort-async-release".to_owned())
+				.spawn(move || drop(ManuallyDrop::into_inner(ctx)));
+			return;
+		}
+		guard.completion = Some((result, Some(ctx)));
+		let waker = guard.waker.take();
+		drop(guard);
+		if let Some(waker) = waker {
+			waker.wake();
+		}
+	}
+}
+
+impl Session {
+	/// Asynchronously run the input data through the ONNX graph, returning a [`Future`] resolving to the session's
+	/// outputs. The future does not depend on any particular async runtime.
+	///
+	/// Inference is performed on the session's intra-op thread pool, which must have at least 2 threads (see
+	/// [`SessionBuilder::with_intra_threads`](crate::SessionBuilder::with_intra_threads)).
+	///
+	/// Dropping the returned future before it completes will terminate the run.
+	///
+	/// ```no_run
+	/// # use ort::Session;
+	/// # async fn infer() -> ort::Result<()> {
+	/// let session = Session::builder()?.with_intra_threads(2)?.with_model_from_file("model.onnx")?;
+	/// let input = ndarray::Array1::from_vec(vec![1.0_f32, 2.0, 3.0]);
+	/// let outputs = session.run_async(ort::inputs![input]?).await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn run_async<const N: usize>(&self, input_values: impl Into<SessionInputs<'static, N>>) -> InferenceFut<'_> {
+		let run_options = match RunOptions::new() {
+			Ok(run_options) => run_options,
+			Err(e) => return InferenceFut::failed(e)
+		};
+		let mut fut = self.run_async_inner(input_values.into(), &run_options, HashMap::new());
+		fut.owned_run_handle = Some(run_options.handle());
+		fut
+	}
+
+	/// Asynchronously run the input data through the ONNX graph, writing the outputs named in `outputs` into the given
+	/// pre-allocated [`Value`]s. See [`Session::run_async`] & [`Session::run_with_outputs`].
+	///
+	/// As with [`Session::run_with_outputs`], the run takes ownership of the pre-allocated values and hands them back
+	/// in the returned [`SessionOutputs`], so they can be reused by removing them from the outputs. The values stay
+	/// owned by the run until it completes, as ONNX Runtime writes to them from another thread.
+	///
+	/// ```no_run
+	/// # use std::collections::HashMap;
+	/// # use ort::{Session, Value};
+	/// # async fn infer() -> ort::Result<()> {
+	/// let session = Session::builder()?.with_intra_threads(2)?.with_model_from_file("model.onnx")?;
+	/// let mut output = Value::from_array(ndarray::Array2::<f32>::zeros((1, 1000)))?;
+	/// for _ in 0..100 {
+	/// 	let input = ndarray::Array4::<f32>::zeros((1, 3, 224, 224));
+	/// 	let mut outputs = session
+	/// 		.run_async_with_outputs(ort::inputs![input]?, HashMap::from([("output".to_string(), output)]))
+	/// 		.await?;
+	/// 	output = outputs.remove("output").expect("pre-allocated output should be handed back");
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn run_async_with_outputs<const N: usize>(
+		&self,
+		input_values: impl Into<SessionInputs<'static, N>>,
+		outputs: HashMap<String, Value>
+	) -> InferenceFut<'_> {
+		let run_options = match RunOptions::new() {
+			Ok(run_options) => run_options,
+			Err(e) => return InferenceFut::failed(e)
+		};
+		let mut fut = self.run_async_inner(input_values.into(), &run_options, outputs);
+		fut.owned_run_handle = Some(run_options.handle());
+		fut
+	}
+
+	/// Asynchronously run the input data through the ONNX graph with the given [`RunOptions`]. See
+	/// [`Session::run_async`].
+	///
+	/// Unlike [`Session::run_async`], dropping the returned future will **not** terminate the run, since the run
+	/// options may be shared with other runs. Use [`RunOptions::terminate`] to cancel the run instead.
+	pub fn run_async_with_options<const N: usize>(&self, input_values: impl Into<SessionInputs<'static, N>>, run_options: &RunOptions) -> InferenceFut<'_> {
+		self.run_async_inner(input_values.into(), run_options, HashMap::new())
+	}
+
+	fn run_async_inner<const N: usize>(
+		&self,
+		input_values: SessionInputs<'static, N>,
+		run_options: &RunOptions,
+		preallocated_outputs: HashMap<String, Value>
+	) -> InferenceFut<'_> {
+		let validate = |input_names: &[Cow<'_, str>], input_values: &[Value]| {
+			if self.validate_inputs {
+				validate_inputs(self, &input_names.iter().map(|name| name.as_ref()).collect::<Vec<_>>(), input_values)
+			} else {
+				Ok(())
+			}
+		};
+		let (input_names, input_ort_values, input_values): (Vec<Cow<'_, str>>, Vec<*const ort_sys::OrtValue>, Vec<Value>) = match input_values {
+			// `'static` slices will outlive the run, so there is no need to take ownership of them
+			SessionInputs::ValueSlice(input_values) => {
+				let input_names: Vec<Cow<'_, str>> = self.inputs.iter().map(|input| Cow::Borrowed(input.name.as_str())).collect();
+				if let Err(e) = validate(&input_names, input_values) {
+					return InferenceFut::failed(e);
+				}
+				(input_names, input_values.iter().map(|v| v.ptr() as *const _).collect(), Vec::new())
+			}
+			SessionInputs::ValueArray(input_values) => {
+				let input_names: Vec<Cow<'_, str>> = self.inputs.iter().map(|input| Cow::Borrowed(input.name.as_str())).collect();
+				if let Err(e) = validate(&input_names, &input_values) {
+					return InferenceFut::failed(e);
+				}
+				(input_names, input_values.iter().map(|v| v.ptr() as *const _).collect(), input_values.into())
+			}
+			SessionInputs::ValueMap(input_values) => {
+				let (input_names, input_values): (Vec<Cow<'_, str>>, Vec<Value>) = input_values.into_iter().unzip();
+				if let Err(e) = validate(&input_names, &input_values) {
+					return InferenceFut::failed(e);
+				}
+				(input_names, input_values.iter().map(|v| v.ptr() as *const _).collect(), input_values)
+			}
+		};
+
+		let input_names = match input_names
+			.into_iter()
+			.map(|name| CString::new(name.into_owned()))
+			.collect::<Result<Vec<_>, _>>()
+		{
+			Ok(input_names) => input_names,
+			Err(e) => return InferenceFut::failed(e.into())
+		};
+		let mut selected_outputs = match self.selected_outputs(Some(run_options)) {
+			Ok(selected_outputs) => selected_outputs,
+			Err(e) => return InferenceFut::failed(e)
+		};
+		// Pre-allocated outputs are passed to ONNX Runtime after the outputs it should allocate.
+		let (preallocated_names, preallocated_values): (Vec<String>, Vec<Value>) = preallocated_outputs.into_iter().unzip();
+		match self.exclude_preallocated_outputs(&mut selected_outputs, preallocated_names.iter()) {
+			Ok(preallocated_names) => selected_outputs.extend(preallocated_names),
+			Err(e) => return InferenceFut::failed(e)
+		}
+		let output_names = match selected_outputs.iter().map(|&name| CString::new(name)).collect::<Result<Vec<_>, _>>() {
+			Ok(output_names) => output_names,
+			Err(e) => return InferenceFut::failed(e.into())
+		};
+
+		let preallocated_outputs: Vec<Option<Value>> = std::iter::repeat_with(|| None)
+			.take(selected_outputs.len() - preallocated_values.len())
+			.chain(preallocated_values.into_iter().map(Some))
+			.collect();
+
+		let state = Arc::new(Mutex::new(InferenceFutState::default()));
+		let mut ctx = Box::new(AsyncInferenceContext {
+			state: Some(Arc::clone(&state)),
+			session_inner: Arc::clone(&self.inner),
+			run_handle: run_options.handle(),
+			input_name_ptrs: input_names.iter().map(|n| n.as_ptr()).collect(),
+			output_name_ptrs: output_names.iter().map(|n| n.as_ptr()).collect(),
+			output_value_ptrs: preallocated_outputs
+				.iter()
+				.map(|value| value.as_ref().map_or(ptr::null_mut(), |v| v.ptr()))
+				.collect(),
+			preallocated_outputs,
+			input_ort_values,
+			_input_values: input_values,
+			_input_names: input_names,
+			_output_names: output_names
+		});
+
+		let session_ptr = self.inner.session_ptr;
+		let run_options_ptr = run_options.ptr() as *const _;
+		let input_name_ptrs = ctx.input_name_ptrs.as_ptr();
+		let input_ort_values = ctx.input_ort_values.as_ptr();
+		let input_len = ctx.input_ort_values.len();
+		let output_name_ptrs = ctx.output_name_ptrs.as_ptr();
+		let output_len = ctx.output_name_ptrs.len();
+		let output_value_ptrs = ctx.output_value_ptrs.as_mut_ptr();
+		let ctx_ptr = Box::into_raw(ctx);
+
+		let status = ortsys![
+			unsafe RunAsync(
+				session_ptr,
+				run_options_ptr,
+				input_name_ptrs,
+				input_ort_values,
+				input_len as _,
+				output_name_ptrs,
+				output_len as _,
+				output_value_ptrs,
+				Some(async_callback),
+				ctx_ptr as *mut c_void
+			)
+		];
+		if let Err(e) = status_to_result(status) {
+			// the callback will not be called if the run failed to launch, so we have to reclaim the context ourselves
+			drop(unsafe { Box::from_raw(ctx_ptr) });
+			return InferenceFut::failed(Error::SessionRun(e));
+		}
+
+		InferenceFut {
+			output_names: selected_outputs,
+			state,
+			owned_run_handle: None,
+			received: false
+		}
+	}
+}
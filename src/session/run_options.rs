@@ -0,0 +1,341 @@
+use std::{
+	ffi::CString,
+	os::raw::c_char,
+	ptr,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc
+	}
+};
+
+use super::Output;
+use crate::{char_p_to_string, error::status_to_result, ortsys, Error, ErrorInternal, LogLevel, Result};
+
+/// Selects which outputs of a session should be computed by a run. Use with [`RunOptions::set_outputs`].
+///
+/// By default, all of the session's outputs are selected. Computing only a subset of the outputs allows ONNX Runtime
+/// to skip parts of the graph that are not needed to produce them.
+///
+/// ```no_run
+/// # use ort::{OutputSelector, RunOptions, Session};
+/// # fn main() -> ort::Result<()> {
+/// # let session = Session::builder()?.with_model_from_file("gpt2.onnx")?;
+/// # let input = ndarray::Array1::from_vec(vec![1_i64, 2, 3]);
+/// let mut options = RunOptions::new()?;
+/// options.set_outputs(OutputSelector::no_default().with("output1"));
+///
+/// let outputs = session.run_with_options(ort::inputs![input]?, &options)?;
+/// assert_eq!(outputs.len(), 1);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct OutputSelector {
+	use_defaults: bool,
+	default_blocklist: Vec<String>,
+	allowlist: Vec<String>
+}
+
+impl Default for OutputSelector {
+	/// Creates an [`OutputSelector`] selecting all of the session's outputs.
+	fn default() -> Self {
+		Self {
+			use_defaults: true,
+			default_blocklist: Vec::new(),
+			allowlist: Vec::new()
+		}
+	}
+}
+
+impl OutputSelector {
+	/// Creates an [`OutputSelector`] that selects no outputs by default. Outputs to compute must be added with
+	/// [`OutputSelector::with`].
+	pub fn no_default() -> Self {
+		Self {
+			use_defaults: false,
+			..Default::default()
+		}
+	}
+
+	/// Marks the output with the given name to be computed.
+	pub fn with(mut self, name: impl Into<String>) -> Self {
+		self.allowlist.push(name.into());
+		self
+	}
+
+	/// Excludes the output with the given name from the session's default outputs.
+	pub fn without(mut self, name: impl Into<String>) -> Self {
+		self.default_blocklist.push(name.into());
+		self
+	}
+
+	/// Resolves the selected output names against the session's outputs, preserving the order of the session's
+	/// outputs for default outputs, followed by explicitly selected outputs.
+	pub(crate) fn resolve<'s>(&self, outputs: &'s [Output]) -> Result<Vec<&'s str>> {
+		let mut selected: Vec<&'s str> = if self.use_defaults {
+			outputs
+				.iter()
+				.map(|o| o.name.as_str())
+				.filter(|n| !self.default_blocklist.iter().any(|b| b == n))
+				.collect()
+		} else {
+			Vec::new()
+		};
+		for name in &self.allowlist {
+			let name = outputs
+				.iter()
+				.map(|o| o.name.as_str())
+				.find(|n| n == name)
+				.ok_or_else(|| Error::UnknownOutput(name.clone()))?;
+			if !selected.contains(&name) {
+				selected.push(name);
+			}
+		}
+		Ok(selected)
+	}
+}
+
+/// Per-run configuration for [`Session::run_with_options`](crate::Session::run_with_options) and
+/// [`IoBinding::run_with_options`](crate::IoBinding::run_with_options).
+///
+/// A single [`RunOptions`] can be reused across many runs.
+///
+/// ```no_run
+/// # use ort::{LogLevel, RunOptions, Session};
+/// # fn main() -> ort::Result<()> {
+/// # let session = Session::builder()?.with_model_from_file("model.onnx")?;
+/// # let input = ndarray::Array1::from_vec(vec![1.0_f32, 2.0, 3.0]);
+/// let mut options = RunOptions::new()?;
+/// options.set_tag("request-5b3fd1")?;
+/// options.set_log_level(LogLevel::Warning)?;
+///
+/// let outputs = session.run_with_options(ort::inputs![input]?, &options)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct RunOptions {
+	inner: Arc<RunOptionsInner>,
+	pub(crate) outputs: OutputSelector
+}
+
+#[derive(Debug)]
+struct RunOptionsInner {
+	run_options_ptr: *mut ort_sys::OrtRunOptions,
+	terminated: AtomicBool
+}
+
+// `OrtRunOptions` is safe to share between threads; the terminate flag in particular is designed to be set from a
+// different thread than the one performing the run.
+unsafe impl Send for RunOptionsInner {}
+unsafe impl Sync for RunOptionsInner {}
+
+impl RunOptionsInner {
+	fn terminate(&self) -> Result<()> {
+		// the flag must be set before ONNX Runtime can abort a run, so that the aborted run always maps its error to
+		// `Error::RunTerminated`
+		self.terminated.store(true, Ordering::Release);
+		status_to_result(ortsys![unsafe RunOptionsSetTerminate(self.run_options_ptr)]).map_err(|e| {
+			self.terminated.store(false, Ordering::Release);
+			Error::RunOptionsSetTerminate(e)
+		})
+	}
+
+	fn unterminate(&self) -> Result<()> {
+		self.terminated.store(false, Ordering::Release);
+		status_to_result(ortsys![unsafe RunOptionsUnsetTerminate(self.run_options_ptr)]).map_err(|e| {
+			self.terminated.store(true, Ordering::Release);
+			Error::RunOptionsUnsetTerminate(e)
+		})
+	}
+}
+
+impl Drop for RunOptionsInner {
+	fn drop(&mut self) {
+		if !self.run_options_ptr.is_null() {
+			ortsys![unsafe ReleaseRunOptions(self.run_options_ptr)];
+		}
+		self.run_options_ptr = ptr::null_mut();
+	}
+}
+
+impl RunOptions {
+	/// Creates a new [`RunOptions`] with ONNX Runtime's default settings.
+	pub fn new() -> Result<Self> {
+		let mut run_options_ptr: *mut ort_sys::OrtRunOptions = ptr::null_mut();
+		ortsys![unsafe CreateRunOptions(&mut run_options_ptr) -> Error::CreateRunOptions; nonNull(run_options_ptr)];
+		Ok(Self {
+			inner: Arc::new(RunOptionsInner {
+				run_options_ptr,
+				terminated: AtomicBool::new(false)
+			}),
+			outputs: OutputSelector::default()
+		})
+	}
+
+	pub(crate) fn ptr(&self) -> *mut ort_sys::OrtRunOptions {
+		self.inner.run_options_ptr
+	}
+
+	/// Selects which of the session's outputs should be computed by runs using these options. See [`OutputSelector`].
+	///
+	/// This has no effect on [`IoBinding`](crate::IoBinding) runs, which always compute the bound outputs.
+	pub fn set_outputs(&mut self, outputs: OutputSelector) {
+		self.outputs = outputs;
+	}
+
+	/// Returns the [`OutputSelector`] used by runs with these options.
+	pub fn outputs(&self) -> &OutputSelector {
+		&self.outputs
+	}
+
+	/// Returns a [`RunHandle`] which can be used to terminate runs using these options from another thread.
+	pub fn handle(&self) -> RunHandle {
+		RunHandle { inner: Arc::clone(&self.inner) }
+	}
+
+	/// Signals all runs currently using these options to terminate as soon as possible. Those runs will return
+	/// [`Error::RunTerminated`].
+	///
+	/// The flag stays set until [`RunOptions::unterminate`] is called; any run started with these options in the
+	/// meantime will also be terminated.
+	pub fn terminate(&self) -> Result<()> {
+		self.inner.terminate()
+	}
+
+	/// Clears the terminate flag set by [`RunOptions::terminate`], allowing these options to be used for new runs.
+	pub fn unterminate(&self) -> Result<()> {
+		self.inner.unterminate()
+	}
+
+	/// Returns `true` if [`RunOptions::terminate`] has been called without a subsequent [`RunOptions::unterminate`].
+	pub fn is_terminated(&self) -> bool {
+		self.inner.terminated.load(Ordering::Acquire)
+	}
+
+	/// Sets a tag identifying this run in ONNX Runtime's logs.
+	pub fn set_tag(&mut self, tag: impl AsRef<str>) -> Result<()> {
+		let tag = CString::new(tag.as_ref())?;
+		ortsys![unsafe RunOptionsSetRunTag(self.ptr(), tag.as_ptr()) -> Error::RunOptionsSetTag];
+		Ok(())
+	}
+
+	/// Returns the tag set by [`RunOptions::set_tag`], or an empty string if no tag was set.
+	pub fn tag(&self) -> Result<String> {
+		let mut tag_ptr: *const c_char = ptr::null();
+		ortsys![unsafe RunOptionsGetRunTag(self.ptr(), &mut tag_ptr) -> Error::RunOptionsGetTag];
+		// the tag is owned by the run options; it must not be freed
+		if tag_ptr.is_null() {
+			Ok(String::new())
+		} else {
+			char_p_to_string(tag_ptr)
+		}
+	}
+
+	/// Sets the minimum severity of log messages emitted during this run.
+	pub fn set_log_level(&mut self, level: LogLevel) -> Result<()> {
+		let level: ort_sys::OrtLoggingLevel = level.into();
+		ortsys![unsafe RunOptionsSetRunLogSeverityLevel(self.ptr(), level as _) -> Error::RunOptionsSetLogLevel];
+		Ok(())
+	}
+
+	/// Adds a configuration entry for this run. See ONNX Runtime's
+	/// [`onnxruntime_run_options_config_keys.h`](https://github.com/microsoft/onnxruntime/blob/v1.16.3/include/onnxruntime/core/session/onnxruntime_run_options_config_keys.h)
+	/// for the list of available keys.
+	pub fn add_config_entry(&mut self, key: impl AsRef<str>, value: impl AsRef<str>) -> Result<()> {
+		let key = CString::new(key.as_ref())?;
+		let value = CString::new(value.as_ref())?;
+		ortsys![unsafe AddRunConfigEntry(self.ptr(), key.as_ptr(), value.as_ptr()) -> Error::RunOptionsAddConfigEntry];
+		Ok(())
+	}
+}
+
+/// Maps an error returned by a failed run to [`Error::RunTerminated`] if the run was terminated via `run_options`.
+pub(crate) fn map_run_error<'r>(
+	run_options: Option<&'r RunOptions>,
+	error: impl FnOnce(ErrorInternal) -> Error + 'r
+) -> impl FnOnce(ErrorInternal) -> Error + 'r {
+	move |e| {
+		if run_options.map(RunOptions::is_terminated).unwrap_or(false) {
+			Error::RunTerminated
+		} else {
+			error(e)
+		}
+	}
+}
+
+/// A cloneable handle used to terminate in-flight runs from another thread, obtained via [`RunOptions::handle`].
+///
+/// ```no_run
+/// # use std::{thread, time::Duration};
+/// # use ort::{RunOptions, Session};
+/// # fn main() -> ort::Result<()> {
+/// # let session = Session::builder()?.with_model_from_file("model.onnx")?;
+/// # let input = ndarray::Array1::from_vec(vec![1.0_f32, 2.0, 3.0]);
+/// let options = RunOptions::new()?;
+/// let handle = options.handle();
+/// thread::spawn(move || {
+/// 	thread::sleep(Duration::from_secs(5));
+/// 	handle.terminate().unwrap();
+/// });
+///
+/// match session.run_with_options(ort::inputs![input]?, &options) {
+/// 	Err(ort::Error::RunTerminated) => eprintln!("inference timed out"),
+/// 	other => {
+/// 		other?;
+/// 	}
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RunHandle {
+	inner: Arc<RunOptionsInner>
+}
+
+impl RunHandle {
+	/// Signals all runs using the associated [`RunOptions`] to terminate as soon as possible. See
+	/// [`RunOptions::terminate`].
+	pub fn terminate(&self) -> Result<()> {
+		self.inner.terminate()
+	}
+
+	/// Clears the terminate flag. See [`RunOptions::unterminate`].
+	pub fn unterminate(&self) -> Result<()> {
+		self.inner.unterminate()
+	}
+
+	/// Returns `true` if the associated [`RunOptions`] have been terminated.
+	pub fn is_terminated(&self) -> bool {
+		self.inner.terminated.load(Ordering::Acquire)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{TensorElementDataType, ValueType};
+
+	fn outputs() -> Vec<Output> {
+		["logits", "present.0", "present.1"]
+			.into_iter()
+			.map(|name| Output {
+				name: name.to_owned(),
+				output_type: ValueType::Tensor {
+					ty: TensorElementDataType::Float32,
+					dimensions: vec![-1],
+					dimension_symbols: vec![Some("batch".to_owned())]
+				}
+			})
+			.collect()
+	}
+
+	#[test]
+	fn test_output_selector() {
+		let outputs = outputs();
+		assert_eq!(OutputSelector::default().resolve(&outputs).unwrap(), ["logits", "present.0", "present.1"]);
+		assert_eq!(OutputSelector::default().without("present.0").resolve(&outputs).unwrap(), ["logits", "present.1"]);
+		assert_eq!(OutputSelector::no_default().with("present.1").with("logits").resolve(&outputs).unwrap(), ["present.1", "logits"]);
+		assert!(matches!(OutputSelector::no_default().with("nonexistent").resolve(&outputs), Err(Error::UnknownOutput(name)) if name == "nonexistent"));
+	}
+}
@@ -0,0 +1,226 @@
+use std::{marker::PhantomData, ops::Deref, sync::Arc};
+
+use super::{OutputSelector, RunOptions, Session, SessionInputs, SessionOutputs};
+use crate::{Error, IntoTensorElementDataType, Result, TensorElementDataType, Value, ValueType};
+
+/// A type which can be used as a field of an [`Inputs`] or [`Outputs`] struct, describing the tensor it expects so that
+/// [`TypedSession::new`] can check it against the model.
+///
+/// Both methods default to `None`, which accepts any type.
+pub trait TypedField {
+	/// The element type of the tensor this field expects, or `None` if any type is accepted.
+	fn element_type() -> Option<TensorElementDataType> {
+		None
+	}
+
+	/// The number of dimensions of the tensor this field expects, or `None` if any number of dimensions is accepted.
+	fn rank() -> Option<usize> {
+		None
+	}
+}
+
+impl TypedField for Value {}
+
+#[cfg(feature = "ndarray")]
+impl<S: ndarray::RawData, D: ndarray::Dimension> TypedField for ndarray::ArrayBase<S, D>
+where
+	S::Elem: IntoTensorElementDataType
+{
+	fn element_type() -> Option<TensorElementDataType> {
+		Some(S::Elem::into_tensor_element_data_type())
+	}
+
+	fn rank() -> Option<usize> {
+		D::NDIM
+	}
+}
+
+impl<T: IntoTensorElementDataType> TypedField for (Vec<i64>, Arc<Box<[T]>>) {
+	fn element_type() -> Option<TensorElementDataType> {
+		Some(T::into_tensor_element_data_type())
+	}
+}
+
+impl<T: TypedField + ?Sized> TypedField for &T {
+	fn element_type() -> Option<TensorElementDataType> {
+		T::element_type()
+	}
+
+	fn rank() -> Option<usize> {
+		T::rank()
+	}
+}
+
+impl<T: TypedField + ?Sized> TypedField for &mut T {
+	fn element_type() -> Option<TensorElementDataType> {
+		T::element_type()
+	}
+
+	fn rank() -> Option<usize> {
+		T::rank()
+	}
+}
+
+/// The element type & number of dimensions a field of an [`Inputs`] or [`Outputs`] struct expects; see [`TypedField`].
+pub type FieldType = (Option<TensorElementDataType>, Option<usize>);
+
+/// Checks that a model input or output of type `actual` can be used with a field expecting `field_type`.
+fn check_field_type(name: &str, (element_type, rank): FieldType, actual: &ValueType) -> Result<()> {
+	let Some(expected) = element_type else {
+		return Ok(());
+	};
+	let dimensions = match actual {
+		ValueType::Tensor { ty, dimensions, .. } if *ty == expected => dimensions,
+		_ => {
+			return Err(Error::TypedDataTypeMismatch {
+				name: name.to_string(),
+				expected,
+				actual: actual.clone()
+			});
+		}
+	};
+	// models without shape information report no dimensions, so only check the rank if the model has one
+	match rank {
+		Some(expected) if !dimensions.is_empty() && dimensions.len() != expected => Err(Error::TypedRankMismatch {
+			name: name.to_string(),
+			expected,
+			actual: dimensions.len()
+		}),
+		_ => Ok(())
+	}
+}
+
+/// A struct which can be converted into a session's named inputs.
+///
+/// This is typically implemented with `#[derive(ort::Inputs)]` (requires the `macros` feature), which maps each field
+/// to the input of the same name (or the name given by `#[ort(name = "...")]`) and converts it into a
+/// [`Value`](crate::Value) via `Value::try_from`. Fields must also implement [`TypedField`].
+///
+/// ```ignore
+/// #[derive(ort::Inputs)]
+/// struct TextInputs {
+/// 	input_ids: ndarray::Array2<i64>,
+/// 	#[ort(name = "attention_mask")]
+/// 	mask: ndarray::Array2<i64>
+/// }
+/// ```
+pub trait Inputs {
+	/// The names of the model inputs this struct provides.
+	const NAMES: &'static [&'static str];
+
+	/// The type expected for each input in [`Inputs::NAMES`], in the same order. Inputs without a type are not checked.
+	fn field_types() -> Vec<FieldType> {
+		Vec::new()
+	}
+
+	/// Converts this struct into [`SessionInputs`].
+	fn into_session_inputs(self) -> Result<SessionInputs<'static>>;
+}
+
+/// A struct which can be constructed from a session's named outputs.
+///
+/// This is typically implemented with `#[derive(ort::Outputs)]` (requires the `macros` feature), which maps each
+/// field to the output of the same name (or the name given by `#[ort(name = "...")]`). Fields must implement
+/// `TryFrom<Value>`, which is implemented for [`Value`](crate::Value) itself and for `ndarray::ArrayD<T>`, as well as
+/// [`TypedField`].
+pub trait Outputs: Sized {
+	/// The names of the model outputs this struct requires.
+	const NAMES: &'static [&'static str];
+
+	/// The type expected for each output in [`Outputs::NAMES`], in the same order. Outputs without a type are not
+	/// checked.
+	fn field_types() -> Vec<FieldType> {
+		Vec::new()
+	}
+
+	/// Constructs this struct by taking its fields out of `outputs`.
+	fn from_session_outputs(outputs: SessionOutputs<'_>) -> Result<Self>;
+}
+
+/// A [`Session`] whose inputs & outputs are described by the [`Inputs`] and [`Outputs`] types `I` and `O`.
+///
+/// The session's signature is checked against `I` & `O` when the `TypedSession` is created, so a model which does not
+/// match the expected signature is caught at load time rather than on the first run.
+///
+/// ```ignore
+/// let session = TypedSession::<TextInputs, TextOutputs>::new(Session::builder()?.with_model_from_file("model.onnx")?)?;
+/// let outputs = session.run(TextInputs { input_ids, mask })?;
+/// ```
+#[derive(Debug)]
+pub struct TypedSession<I: Inputs, O: Outputs> {
+	session: Session,
+	run_options: RunOptions,
+	_types: PhantomData<fn(I) -> O>
+}
+
+impl<I: Inputs, O: Outputs> TypedSession<I, O> {
+	/// Wraps `session`, checking that its inputs & outputs match `I` & `O`.
+	///
+	/// Returns [`Error::UnknownInput`] if `I` provides an input the model does not have, [`Error::MissingInput`] if `I`
+	/// does not provide one of the model's inputs, and [`Error::UnknownOutput`] if `O` requires an output the model
+	/// does not have. Returns [`Error::TypedDataTypeMismatch`] or [`Error::TypedRankMismatch`] if a field expects a
+	/// tensor with a different element type or number of dimensions than the model's input or output.
+	pub fn new(session: Session) -> Result<Self> {
+		if let Some(name) = I::NAMES.iter().find(|name| {
+			!session
+				.inputs
+				.iter()
+				.chain(&session.overridable_initializers)
+				.any(|input| input.name == **name)
+		}) {
+			return Err(Error::UnknownInput(name.to_string()));
+		}
+		if let Some(input) = session.inputs.iter().find(|input| !I::NAMES.contains(&input.name.as_str())) {
+			return Err(Error::MissingInput(input.name.clone()));
+		}
+		for (name, field_type) in I::NAMES.iter().zip(I::field_types()) {
+			if let Some(input) = session
+				.inputs
+				.iter()
+				.chain(&session.overridable_initializers)
+				.find(|input| input.name == *name)
+			{
+				check_field_type(name, field_type, &input.input_type)?;
+			}
+		}
+
+		// only compute the outputs we actually use
+		let mut outputs = OutputSelector::no_default();
+		let mut field_types = O::field_types().into_iter();
+		for name in O::NAMES {
+			let Some(output) = session.outputs.iter().find(|output| output.name == *name) else {
+				return Err(Error::UnknownOutput(name.to_string()));
+			};
+			if let Some(field_type) = field_types.next() {
+				check_field_type(name, field_type, &output.output_type)?;
+			}
+			outputs = outputs.with(*name);
+		}
+		let mut run_options = RunOptions::new()?;
+		run_options.set_outputs(outputs);
+
+		Ok(Self {
+			session,
+			run_options,
+			_types: PhantomData
+		})
+	}
+
+	/// Run the typed inputs through the model, returning the typed outputs.
+	pub fn run(&self, inputs: I) -> Result<O> {
+		O::from_session_outputs(self.session.run_with_options(inputs.into_session_inputs()?, &self.run_options)?)
+	}
+
+	/// Returns the underlying [`Session`].
+	pub fn into_inner(self) -> Session {
+		self.session
+	}
+}
+
+impl<I: Inputs, O: Outputs> Deref for TypedSession<I, O> {
+	type Target = Session;
+
+	fn deref(&self) -> &Self::Target {
+		&self.session
+	}
+}
@@ -16,6 +16,7 @@
 
 #[cfg(feature = "ndarray")]
 mod ndarray;
+mod sparse;
 mod types;
 
 use std::{fmt::Debug, ptr};
@@ -23,12 +24,12 @@ use std::{fmt::Debug, ptr};
 #[cfg(feature = "ndarray")]
 use ::ndarray::{ArrayView, IxDyn};
 
+pub use self::sparse::{SparseFormat, SparseIndices, SparseTensor};
+pub(crate) use self::types::extract_strings;
 pub use self::types::{ExtractTensorData, IntoTensorElementDataType, TensorElementDataType, Utf8Data};
 #[cfg(feature = "ndarray")]
 pub use self::{ndarray::ArrayExtensions, types::TensorData};
 use crate::ortsys;
-#[cfg(feature = "ndarray")]
-use crate::{Error, Result};
 
 /// Tensor containing data owned by the ONNX Runtime C library, used to return values from inference.
 ///
@@ -0,0 +1,396 @@
+use std::{ffi::c_void, ptr, slice};
+
+use super::{ExtractTensorData, IntoTensorElementDataType, TensorElementDataType};
+use crate::{
+	memory::{Allocator, MemoryInfo},
+	ortsys, AllocatorType, Error, MemType, Result, Value
+};
+
+/// The format of a [`SparseTensor`]'s indices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SparseFormat {
+	/// Coordinate format.
+	Coo,
+	/// Compressed sparse row format.
+	Csr,
+	/// Block sparse format.
+	BlockSparse
+}
+
+/// The indices of a [`SparseTensor`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SparseIndices<'a> {
+	/// Coordinate indices; either one linear index into the flattened dense tensor per value, or a `[nnz, rank]`
+	/// array with the full coordinates of each value.
+	Coo(&'a [i64]),
+	/// Compressed sparse row indices for a 2-dimensional tensor. `inner` contains the column index of each value, and
+	/// `outer` contains the offset of each row's first value in `inner`, plus the total number of values.
+	Csr { inner: &'a [i64], outer: &'a [i64] },
+	/// Block sparse indices of shape `[2, num_blocks]`, containing the row & column (in blocks) of each block.
+	BlockSparse { shape: Vec<i64>, indices: &'a [i32] }
+}
+
+impl<'a> SparseIndices<'a> {
+	/// Returns the format of these indices.
+	pub fn format(&self) -> SparseFormat {
+		match self {
+			SparseIndices::Coo(_) => SparseFormat::Coo,
+			SparseIndices::Csr { .. } => SparseFormat::Csr,
+			SparseIndices::BlockSparse { .. } => SparseFormat::BlockSparse
+		}
+	}
+}
+
+/// A sparse tensor, consisting of the shape of the dense tensor it represents, its non-zero values, and their indices.
+///
+/// Sparse tensors are converted into [`Value`]s with [`Value::from_sparse_tensor`], which copies the values & indices,
+/// and extracted from sparse [`Value`]s with [`Value::extract_sparse_tensor`], which borrows them.
+///
+/// ```no_run
+/// # use ort::{SparseTensor, Value};
+/// # fn main() -> ort::Result<()> {
+/// // [[0, 1, 0], [2, 0, 0]]
+/// let values = [1.0_f32, 2.0];
+/// let coo = Value::from_sparse_tensor(&SparseTensor::coo([2, 3], &values, &[1, 3])?)?;
+/// let csr = Value::from_sparse_tensor(&SparseTensor::csr([2, 3], &values, &[1, 0], &[0, 1, 2])?)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseTensor<'a, T> {
+	dense_shape: Vec<i64>,
+	values_shape: Vec<i64>,
+	values: &'a [T],
+	indices: SparseIndices<'a>
+}
+
+impl<'a, T> SparseTensor<'a, T> {
+	/// Creates a sparse tensor in the coordinate format. See [`SparseIndices::Coo`].
+	///
+	/// Returns [`Error::SparseIndicesLengthMismatch`] if there is neither one linear index nor one full coordinate per
+	/// value, and [`Error::SparseIndexOutOfBounds`] if an index lies outside of `dense_shape`.
+	pub fn coo(dense_shape: impl Into<Vec<i64>>, values: &'a [T], indices: &'a [i64]) -> Result<Self> {
+		let tensor = Self {
+			dense_shape: dense_shape.into(),
+			values_shape: vec![values.len() as i64],
+			values,
+			indices: SparseIndices::Coo(indices)
+		};
+		tensor.validate()?;
+		Ok(tensor)
+	}
+
+	/// Creates a sparse tensor in the compressed sparse row format. See [`SparseIndices::Csr`].
+	///
+	/// Returns [`Error::InvalidSparseDenseShape`] if `dense_shape` is not 2-dimensional,
+	/// [`Error::SparseIndicesLengthMismatch`] if there is not one inner index per value & one outer index per row plus
+	/// one, [`Error::SparseIndexOutOfBounds`] if an inner index lies outside of the columns of `dense_shape`, and
+	/// [`Error::InvalidCsrOuterIndices`] if the outer indices are not valid row offsets.
+	pub fn csr(dense_shape: impl Into<Vec<i64>>, values: &'a [T], inner_indices: &'a [i64], outer_indices: &'a [i64]) -> Result<Self> {
+		let tensor = Self {
+			dense_shape: dense_shape.into(),
+			values_shape: vec![values.len() as i64],
+			values,
+			indices: SparseIndices::Csr {
+				inner: inner_indices,
+				outer: outer_indices
+			}
+		};
+		tensor.validate()?;
+		Ok(tensor)
+	}
+
+	/// Creates a sparse tensor in the block sparse format, where `values` contains the blocks in the shape
+	/// `values_shape`, i.e. `[block_rows, block_cols, num_blocks]`. See [`SparseIndices::BlockSparse`].
+	///
+	/// Returns [`Error::SparseTensorShapeMismatch`] if `values_shape` or `indices_shape` do not match the number of
+	/// values or indices.
+	pub fn block_sparse(
+		dense_shape: impl Into<Vec<i64>>,
+		values_shape: impl Into<Vec<i64>>,
+		values: &'a [T],
+		indices_shape: impl Into<Vec<i64>>,
+		indices: &'a [i32]
+	) -> Result<Self> {
+		let tensor = Self {
+			dense_shape: dense_shape.into(),
+			values_shape: values_shape.into(),
+			values,
+			indices: SparseIndices::BlockSparse { shape: indices_shape.into(), indices }
+		};
+		tensor.validate()?;
+		Ok(tensor)
+	}
+
+	/// Checks that the shapes of the values & indices match the number of elements provided, as ONNX Runtime reads as
+	/// many elements as the shapes describe, and that COO & CSR indices lie within the dense shape.
+	fn validate(&self) -> Result<()> {
+		check_shape(&self.values_shape, self.values.len())?;
+		let nnz = self.values.len();
+		match &self.indices {
+			SparseIndices::Coo(indices) => {
+				if self.dense_shape.iter().any(|&dim| dim < 0) {
+					return Err(Error::InvalidSparseDenseShape(self.dense_shape.clone()));
+				}
+				if indices.len() == nnz {
+					let bound = self
+						.dense_shape
+						.iter()
+						.try_fold(1_i64, |bound, &dim| bound.checked_mul(dim))
+						.unwrap_or(i64::MAX);
+					check_bounds(indices, bound)?;
+				} else if indices.len() == nnz * self.dense_shape.len() {
+					for coordinate in indices.chunks_exact(self.dense_shape.len()) {
+						for (&index, &bound) in coordinate.iter().zip(&self.dense_shape) {
+							check_bounds(&[index], bound)?;
+						}
+					}
+				} else {
+					return Err(Error::SparseIndicesLengthMismatch { expected: nnz, len: indices.len() });
+				}
+			}
+			SparseIndices::Csr { inner, outer } => {
+				let &[rows, cols] = self.dense_shape.as_slice() else {
+					return Err(Error::InvalidSparseDenseShape(self.dense_shape.clone()));
+				};
+				if rows < 0 || cols < 0 {
+					return Err(Error::InvalidSparseDenseShape(self.dense_shape.clone()));
+				}
+				if inner.len() != nnz {
+					return Err(Error::SparseIndicesLengthMismatch { expected: nnz, len: inner.len() });
+				}
+				if outer.len() as i64 != rows + 1 {
+					return Err(Error::SparseIndicesLengthMismatch {
+						expected: rows as usize + 1,
+						len: outer.len()
+					});
+				}
+				check_bounds(inner, cols)?;
+				if outer.first() != Some(&0) || outer.last() != Some(&(nnz as i64)) || outer.windows(2).any(|w| w[0] > w[1]) {
+					return Err(Error::InvalidCsrOuterIndices(outer.to_vec()));
+				}
+			}
+			SparseIndices::BlockSparse { shape, indices } => check_shape(shape, indices.len())?
+		}
+		Ok(())
+	}
+
+	/// Returns the format of this tensor's indices.
+	pub fn format(&self) -> SparseFormat {
+		self.indices.format()
+	}
+
+	/// Returns the shape of the dense tensor this sparse tensor represents.
+	pub fn dense_shape(&self) -> &[i64] {
+		&self.dense_shape
+	}
+
+	/// Returns the shape of this tensor's values.
+	pub fn values_shape(&self) -> &[i64] {
+		&self.values_shape
+	}
+
+	/// Returns the non-zero values of this tensor.
+	pub fn values(&self) -> &'a [T] {
+		self.values
+	}
+
+	/// Returns the indices of this tensor's values.
+	pub fn indices(&self) -> &SparseIndices<'a> {
+		&self.indices
+	}
+}
+
+fn check_shape(shape: &[i64], len: usize) -> Result<()> {
+	let expected = shape
+		.iter()
+		.try_fold(1_usize, |expected, &dim| expected.checked_mul(usize::try_from(dim).ok()?));
+	if expected != Some(len) {
+		return Err(Error::SparseTensorShapeMismatch { shape: shape.to_vec(), len });
+	}
+	Ok(())
+}
+
+fn check_bounds(indices: &[i64], bound: i64) -> Result<()> {
+	if let Some(&index) = indices.iter().find(|&&index| !(0..bound).contains(&index)) {
+		return Err(Error::SparseIndexOutOfBounds { index, bound });
+	}
+	Ok(())
+}
+
+/// Returns the dimensions & element count of a tensor type info, releasing it afterwards.
+fn consume_tensor_info(info_ptr: *mut ort_sys::OrtTensorTypeAndShapeInfo) -> Result<(TensorElementDataType, Vec<i64>, usize)> {
+	let res = (|| {
+		let mut type_sys = ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED;
+		ortsys![unsafe GetTensorElementType(info_ptr, &mut type_sys) -> Error::GetTensorElementType];
+		if type_sys == ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED {
+			return Err(Error::UndefinedTensorElementType);
+		}
+
+		let mut num_dims = 0;
+		ortsys![unsafe GetDimensionsCount(info_ptr, &mut num_dims) -> Error::GetDimensionsCount];
+		let mut dims: Vec<i64> = vec![0; num_dims as _];
+		ortsys![unsafe GetDimensions(info_ptr, dims.as_mut_ptr(), num_dims as _) -> Error::GetDimensions];
+
+		let mut len = 0;
+		ortsys![unsafe GetTensorShapeElementCount(info_ptr, &mut len) -> Error::GetTensorShapeElementCount];
+		Ok((type_sys.into(), dims, len as _))
+	})();
+	ortsys![unsafe ReleaseTensorTypeAndShapeInfo(info_ptr)];
+	res
+}
+
+/// Returns the indices of the given format, borrowed from `value`.
+fn sparse_indices<T>(value: &Value, format: ort_sys::OrtSparseIndicesFormat) -> Result<&[T]> {
+	let mut num_indices: ort_sys::size_t = 0;
+	let mut indices_ptr: *const c_void = ptr::null();
+	ortsys![unsafe GetSparseTensorIndices(value.ptr(), format, &mut num_indices, &mut indices_ptr) -> Error::GetSparseTensorIndices];
+	if num_indices == 0 {
+		return Ok(&[]);
+	}
+	Ok(unsafe { slice::from_raw_parts(indices_ptr as *const T, num_indices as _) })
+}
+
+impl Value {
+	/// Construct a sparse tensor [`Value`], copying the values & indices of `tensor`.
+	pub fn from_sparse_tensor<T: IntoTensorElementDataType>(tensor: &SparseTensor<'_, T>) -> Result<Value> {
+		tensor.validate()?;
+
+		let allocator = Allocator::default();
+		let memory_info = MemoryInfo::new_cpu(AllocatorType::Arena, MemType::Default)?;
+
+		let mut value_ptr: *mut ort_sys::OrtValue = ptr::null_mut();
+		ortsys![
+			unsafe CreateSparseTensorAsOrtValue(
+				allocator.ptr,
+				tensor.dense_shape.as_ptr(),
+				tensor.dense_shape.len() as _,
+				T::into_tensor_element_data_type().into(),
+				&mut value_ptr
+			) -> Error::CreateSparseTensor;
+			nonNull(value_ptr)
+		];
+		// the value is created with the default allocator, so it is independent of any session
+		let value = unsafe { Value::from_detached_raw(value_ptr) };
+
+		let values_ptr = tensor.values.as_ptr() as *const c_void;
+		match &tensor.indices {
+			SparseIndices::Coo(indices) => {
+				ortsys![
+					unsafe FillSparseTensorCoo(
+						value_ptr,
+						memory_info.ptr,
+						tensor.values_shape.as_ptr(),
+						tensor.values_shape.len() as _,
+						values_ptr,
+						indices.as_ptr(),
+						indices.len() as _
+					) -> Error::FillSparseTensor
+				];
+			}
+			SparseIndices::Csr { inner, outer } => {
+				ortsys![
+					unsafe FillSparseTensorCsr(
+						value_ptr,
+						memory_info.ptr,
+						tensor.values_shape.as_ptr(),
+						tensor.values_shape.len() as _,
+						values_ptr,
+						inner.as_ptr(),
+						inner.len() as _,
+						outer.as_ptr(),
+						outer.len() as _
+					) -> Error::FillSparseTensor
+				];
+			}
+			SparseIndices::BlockSparse { shape, indices } => {
+				ortsys![
+					unsafe FillSparseTensorBlockSparse(
+						value_ptr,
+						memory_info.ptr,
+						tensor.values_shape.as_ptr(),
+						tensor.values_shape.len() as _,
+						values_ptr,
+						shape.as_ptr(),
+						shape.len() as _,
+						indices.as_ptr()
+					) -> Error::FillSparseTensor
+				];
+			}
+		}
+		Ok(value)
+	}
+
+	/// Returns `true` if this value is a sparse tensor.
+	pub fn is_sparse_tensor(&self) -> Result<bool> {
+		let mut result = 0;
+		ortsys![unsafe IsSparseTensor(self.ptr(), &mut result) -> Error::IsSparseTensor];
+		Ok(result == 1)
+	}
+
+	/// Extracts the values & indices of a sparse tensor value without copying.
+	///
+	/// Returns [`Error::ValueTypeMismatch`] if this value is not a sparse tensor, or [`Error::DataTypeMismatch`] if
+	/// `T` does not match the tensor's element type. String sparse tensors cannot be extracted.
+	pub fn extract_sparse_tensor<T: ExtractTensorData>(&self) -> Result<SparseTensor<'_, T>> {
+		// ONNX Runtime stores strings as `std::string`s, which cannot be borrowed as `String`s
+		if T::tensor_element_data_type() == TensorElementDataType::String {
+			return Err(Error::SparseStringTensor);
+		}
+		if !self.is_sparse_tensor()? {
+			return Err(Error::ValueTypeMismatch {
+				expected: ort_sys::ONNXType::ONNX_TYPE_SPARSETENSOR,
+				actual: self.onnx_type()?
+			});
+		}
+
+		let mut format = ort_sys::OrtSparseFormat::ORT_SPARSE_UNDEFINED;
+		ortsys![unsafe GetSparseTensorFormat(self.ptr(), &mut format) -> Error::GetSparseTensorFormat];
+
+		let mut values_info_ptr: *mut ort_sys::OrtTensorTypeAndShapeInfo = ptr::null_mut();
+		ortsys![unsafe GetSparseTensorValuesTypeAndShape(self.ptr(), &mut values_info_ptr) -> Error::GetSparseTensorValues; nonNull(values_info_ptr)];
+		let (data_type, values_shape, values_len) = consume_tensor_info(values_info_ptr)?;
+		if data_type != T::tensor_element_data_type() {
+			return Err(Error::DataTypeMismatch {
+				actual: data_type,
+				requested: T::tensor_element_data_type()
+			});
+		}
+
+		let values: &[T] = if values_len == 0 {
+			&[]
+		} else {
+			let mut values_ptr: *const c_void = ptr::null();
+			ortsys![unsafe GetSparseTensorValues(self.ptr(), &mut values_ptr) -> Error::GetSparseTensorValues; nonNull(values_ptr)];
+			unsafe { slice::from_raw_parts(values_ptr as *const T, values_len) }
+		};
+
+		let indices = match format {
+			ort_sys::OrtSparseFormat::ORT_SPARSE_COO => SparseIndices::Coo(sparse_indices(self, ort_sys::OrtSparseIndicesFormat::ORT_SPARSE_COO_INDICES)?),
+			ort_sys::OrtSparseFormat::ORT_SPARSE_CSRC => SparseIndices::Csr {
+				inner: sparse_indices(self, ort_sys::OrtSparseIndicesFormat::ORT_SPARSE_CSR_INNER_INDICES)?,
+				outer: sparse_indices(self, ort_sys::OrtSparseIndicesFormat::ORT_SPARSE_CSR_OUTER_INDICES)?
+			},
+			ort_sys::OrtSparseFormat::ORT_SPARSE_BLOCK_SPARSE => {
+				let mut indices_info_ptr: *mut ort_sys::OrtTensorTypeAndShapeInfo = ptr::null_mut();
+				ortsys![
+					unsafe GetSparseTensorIndicesTypeShape(self.ptr(), ort_sys::OrtSparseIndicesFormat::ORT_SPARSE_BLOCK_SPARSE_INDICES, &mut indices_info_ptr) -> Error::GetSparseTensorIndices;
+					nonNull(indices_info_ptr)
+				];
+				let (_, shape, _) = consume_tensor_info(indices_info_ptr)?;
+				SparseIndices::BlockSparse {
+					shape,
+					indices: sparse_indices(self, ort_sys::OrtSparseIndicesFormat::ORT_SPARSE_BLOCK_SPARSE_INDICES)?
+				}
+			}
+			ort_sys::OrtSparseFormat::ORT_SPARSE_UNDEFINED => return Err(Error::UndefinedSparseFormat)
+		};
+
+		Ok(SparseTensor {
+			dense_shape: self.shape()?,
+			values_shape,
+			values,
+			indices
+		})
+	}
+}
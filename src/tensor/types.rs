@@ -1,9 +1,12 @@
-use std::fmt::{self, Debug};
 #[cfg(feature = "ndarray")]
-use std::{ffi, ptr, result, string};
+use std::ptr;
+use std::{
+	ffi,
+	fmt::{self, Debug},
+	result, string
+};
 
-#[cfg(feature = "ndarray")]
-use super::{ortsys, Error, Result};
+use crate::{ortsys, Error, Result};
 
 /// Enum mapping ONNX Runtime's supported tensor data types.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -47,6 +50,21 @@ pub enum TensorElementDataType {
 	Bfloat16
 }
 
+impl TensorElementDataType {
+	/// Returns the size of a single element in bytes, or `None` for variable-size elements (strings).
+	pub(crate) fn byte_size(&self) -> Option<usize> {
+		match self {
+			TensorElementDataType::Uint8 | TensorElementDataType::Int8 | TensorElementDataType::Bool => Some(1),
+			TensorElementDataType::Uint16 | TensorElementDataType::Int16 => Some(2),
+			#[cfg(feature = "half")]
+			TensorElementDataType::Float16 | TensorElementDataType::Bfloat16 => Some(2),
+			TensorElementDataType::Float32 | TensorElementDataType::Int32 | TensorElementDataType::Uint32 => Some(4),
+			TensorElementDataType::Float64 | TensorElementDataType::Int64 | TensorElementDataType::Uint64 => Some(8),
+			TensorElementDataType::String => None
+		}
+	}
+}
+
 impl From<TensorElementDataType> for ort_sys::ONNXTensorElementDataType {
 	fn from(val: TensorElementDataType) -> Self {
 		match val {
@@ -262,36 +280,7 @@ impl ExtractTensorData for String {
 		tensor_element_len: usize,
 		tensor_ptr: *mut ort_sys::OrtValue
 	) -> Result<TensorData<'t, Self>> {
-		// Total length of string data, not including \0 suffix
-		let mut total_length = 0;
-		ortsys![unsafe GetStringTensorDataLength(tensor_ptr, &mut total_length) -> Error::GetStringTensorDataLength];
-
-		// In the JNI impl of this, tensor_element_len was included in addition to total_length,
-		// but that seems contrary to the docs of GetStringTensorDataLength, and those extra bytes
-		// don't seem to be written to in practice either.
-		// If the string data actually did go farther, it would panic below when using the offset
-		// data to get slices for each string.
-		let mut string_contents = vec![0u8; total_length as _];
-		// one extra slot so that the total length can go in the last one, making all per-string
-		// length calculations easy
-		let mut offsets = vec![0; tensor_element_len + 1];
-
-		ortsys![unsafe GetStringTensorContent(tensor_ptr, string_contents.as_mut_ptr() as *mut ffi::c_void, total_length as _, offsets.as_mut_ptr(), tensor_element_len as _) -> Error::GetStringTensorContent];
-
-		// final offset = overall length so that per-string length calculations work for the last string
-		debug_assert_eq!(0, offsets[tensor_element_len]);
-		offsets[tensor_element_len] = total_length;
-
-		let strings = offsets
-            // offsets has 1 extra offset past the end so that all windows work
-            .windows(2)
-            .map(|w| {
-                let slice = &string_contents[w[0] as _..w[1] as _];
-                String::from_utf8(slice.into())
-            })
-            .collect::<result::Result<Vec<String>, string::FromUtf8Error>>()
-            .map_err(Error::StringFromUtf8Error)?;
-
+		let strings = extract_strings(tensor_element_len, tensor_ptr)?;
 		let array = ndarray::Array::from_shape_vec(shape, strings)
 			.expect("Shape extracted from tensor didn't match tensor contents")
 			.into_dyn();
@@ -299,3 +288,36 @@ impl ExtractTensorData for String {
 		Ok(TensorData::Strings { strings: array })
 	}
 }
+
+/// Reads the contents of a string tensor with `tensor_element_len` elements.
+pub(crate) fn extract_strings(tensor_element_len: usize, tensor_ptr: *mut ort_sys::OrtValue) -> Result<Vec<String>> {
+	// Total length of string data, not including \0 suffix
+	let mut total_length = 0;
+	ortsys![unsafe GetStringTensorDataLength(tensor_ptr, &mut total_length) -> Error::GetStringTensorDataLength];
+
+	// In the JNI impl of this, tensor_element_len was included in addition to total_length,
+	// but that seems contrary to the docs of GetStringTensorDataLength, and those extra bytes
+	// don't seem to be written to in practice either.
+	// If the string data actually did go farther, it would panic below when using the offset
+	// data to get slices for each string.
+	let mut string_contents = vec![0u8; total_length as _];
+	// one extra slot so that the total length can go in the last one, making all per-string
+	// length calculations easy
+	let mut offsets = vec![0; tensor_element_len + 1];
+
+	ortsys![unsafe GetStringTensorContent(tensor_ptr, string_contents.as_mut_ptr() as *mut ffi::c_void, total_length as _, offsets.as_mut_ptr(), tensor_element_len as _) -> Error::GetStringTensorContent];
+
+	// final offset = overall length so that per-string length calculations work for the last string
+	debug_assert_eq!(0, offsets[tensor_element_len]);
+	offsets[tensor_element_len] = total_length;
+
+	offsets
+		// offsets has 1 extra offset past the end so that all windows work
+		.windows(2)
+		.map(|w| {
+			let slice = &string_contents[w[0] as _..w[1] as _];
+			String::from_utf8(slice.into())
+		})
+		.collect::<result::Result<Vec<String>, string::FromUtf8Error>>()
+		.map_err(Error::StringFromUtf8Error)
+}
@@ -0,0 +1,44 @@
+use std::{path::Path, ptr};
+
+use super::trainsys;
+use crate::{session::path_to_ortchar, Error, Result};
+
+/// The state of a model being trained: its parameters, and optionally the state of its optimizer.
+///
+/// Checkpoints are loaded from a file generated alongside the training artifacts, passed to a
+/// [`Trainer`](crate::Trainer), and saved periodically to resume training later.
+#[derive(Debug)]
+pub struct Checkpoint {
+	pub(crate) ptr: *mut ort_sys::OrtCheckpointState
+}
+
+unsafe impl Send for Checkpoint {}
+
+impl Checkpoint {
+	/// Loads a checkpoint from a file.
+	pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+		let path = path.as_ref();
+		if !path.exists() {
+			return Err(Error::FileDoesNotExist { filename: path.to_path_buf() });
+		}
+
+		let path = path_to_ortchar(path);
+		let mut ptr: *mut ort_sys::OrtCheckpointState = ptr::null_mut();
+		trainsys![unsafe LoadCheckpoint(path.as_ptr(), &mut ptr) -> Error::LoadCheckpoint; nonNull(ptr)];
+		Ok(Self { ptr })
+	}
+
+	/// Saves the checkpoint to a file. If `include_optimizer_state` is `true`, the state of the optimizer is saved as
+	/// well, so that training can be resumed from the checkpoint exactly where it left off.
+	pub fn save(&self, path: impl AsRef<Path>, include_optimizer_state: bool) -> Result<()> {
+		let path = path_to_ortchar(path.as_ref());
+		trainsys![unsafe SaveCheckpoint(self.ptr, path.as_ptr(), include_optimizer_state) -> Error::SaveCheckpoint];
+		Ok(())
+	}
+}
+
+impl Drop for Checkpoint {
+	fn drop(&mut self) {
+		trainsys![unsafe ReleaseCheckpointState(self.ptr)];
+	}
+}
@@ -0,0 +1,34 @@
+//! Contains [`Checkpoint`] & [`Trainer`] for training ONNX models with ONNX Runtime's on-device training API.
+//!
+//! Training requires ONNX Runtime binaries built with training support, along with training artifacts (a training
+//! model, an eval model, an optimizer model, and an initial checkpoint) generated offline with the
+//! `onnxruntime.training.artifacts` Python module.
+
+pub(crate) mod checkpoint;
+pub(crate) mod trainer;
+
+pub use self::{checkpoint::Checkpoint, trainer::Trainer};
+use crate::{ortsys, Error, Result};
+
+/// Returns the training API of the loaded ONNX Runtime binaries.
+pub(crate) fn training_api() -> Result<&'static ort_sys::OrtTrainingApi> {
+	let api = ortsys![unsafe GetTrainingApi(ort_sys::ORT_API_VERSION)];
+	// `GetTrainingApi` returns null if ONNX Runtime was built without training support
+	unsafe { api.as_ref() }.ok_or(Error::TrainingNotEnabled)
+}
+
+/// Calls a function of the training API, like [`ortsys!`](crate::ortsys) does for the main API.
+macro_rules! trainsys {
+	(unsafe $method:ident($($n:expr),+ $(,)?)) => {
+		unsafe { $crate::training::training_api().expect("training API must be available").$method.unwrap()($($n),+) }
+	};
+	(unsafe $method:ident($($n:expr),+ $(,)?) -> $err:expr$(;)?) => {
+		$crate::error::status_to_result(unsafe { $crate::training::training_api()?.$method.unwrap()($($n),+) }).map_err($err)?;
+	};
+	(unsafe $method:ident($($n:expr),+ $(,)?) -> $err:expr; nonNull($($check:expr),+ $(,)?)$(;)?) => {
+		$crate::error::status_to_result(unsafe { $crate::training::training_api()?.$method.unwrap()($($n),+) }).map_err($err)?;
+		$($crate::error::assert_non_null_pointer($check, stringify!($method))?;)+
+	};
+}
+
+pub(crate) use trainsys;
@@ -0,0 +1,324 @@
+use std::{ffi::CString, os::raw::c_char, path::Path, ptr, sync::atomic::Ordering};
+
+use super::{training_api, trainsys, Checkpoint};
+use crate::{
+	environment::get_environment,
+	error::{assert_non_null_pointer, status_to_result},
+	execution_providers::apply_execution_providers,
+	session::{dangerous::raw_pointer_to_string, path_to_ortchar},
+	Allocator, Error, Result, SessionBuilder, SessionInputs, SessionOutputs, Value
+};
+
+/// Trains a model from the training artifacts generated by `onnxruntime.training.artifacts`.
+///
+/// A training loop typically runs a [`Trainer::train_step`], then an [`Trainer::optimizer_step`] to update the model's
+/// parameters, then a [`Trainer::lazy_reset_grad`] to reset the gradients before the next step.
+///
+/// ```no_run
+/// # use ort::{Checkpoint, Session, Trainer};
+/// # fn main() -> ort::Result<()> {
+/// let trainer = Trainer::new(
+/// 	Session::builder()?,
+/// 	Checkpoint::load("checkpoint")?,
+/// 	"training_model.onnx",
+/// 	"eval_model.onnx",
+/// 	"optimizer_model.onnx"
+/// )?;
+/// trainer.register_linear_lr_scheduler(10, 100, 0.01)?;
+/// for _ in 0..100 {
+/// 	let outputs = trainer
+/// 		.train_step(ort::inputs![ndarray::Array2::<f32>::zeros((1, 8)), ndarray::Array1::<i64>::zeros(1)]?)?;
+/// 	let loss = outputs[0].extract_tensor::<f32>()?.view()[[]];
+/// 	println!("loss: {loss}");
+///
+/// 	trainer.optimizer_step()?;
+/// 	trainer.lazy_reset_grad()?;
+/// 	trainer.scheduler_step()?;
+/// }
+/// trainer.checkpoint().save("checkpoint", true)?;
+/// trainer.export_model_for_inference("inference_model.onnx", &["output"])?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Trainer {
+	ptr: *mut ort_sys::OrtTrainingSession,
+	train_input_names: Vec<String>,
+	train_output_names: Vec<String>,
+	eval_input_names: Vec<String>,
+	eval_output_names: Vec<String>,
+	/// Referenced by the training session, so it must outlive it.
+	checkpoint: Checkpoint,
+	/// Keeps custom operators & initializers registered with the session options alive.
+	_session_options: SessionBuilder
+}
+
+// A training session may be moved between threads, but must not be used from multiple threads at once.
+unsafe impl Send for Trainer {}
+
+impl Trainer {
+	/// Creates a trainer from the training, eval, and optimizer models generated alongside the `checkpoint`. The models
+	/// are loaded with the given session options.
+	pub fn new(
+		session_options: SessionBuilder,
+		checkpoint: Checkpoint,
+		training_model_path: impl AsRef<Path>,
+		eval_model_path: impl AsRef<Path>,
+		optimizer_model_path: impl AsRef<Path>
+	) -> Result<Self> {
+		let api = training_api()?;
+		let model_paths = model_paths([training_model_path.as_ref(), eval_model_path.as_ref(), optimizer_model_path.as_ref()])?;
+
+		let env = get_environment()?;
+		apply_execution_providers(&session_options, session_options.execution_providers.iter().chain(&env.execution_providers).cloned());
+		let env_ptr = env.env_ptr.load(Ordering::Relaxed);
+
+		let mut ptr: *mut ort_sys::OrtTrainingSession = ptr::null_mut();
+		trainsys![
+			unsafe CreateTrainingSession(
+				env_ptr,
+				session_options.session_options_ptr,
+				checkpoint.ptr,
+				model_paths[0].as_ptr(),
+				model_paths[1].as_ptr(),
+				model_paths[2].as_ptr(),
+				&mut ptr
+			) -> Error::CreateTrainingSession;
+			nonNull(ptr)
+		];
+
+		let mut trainer = Self {
+			ptr,
+			train_input_names: Vec::new(),
+			train_output_names: Vec::new(),
+			eval_input_names: Vec::new(),
+			eval_output_names: Vec::new(),
+			checkpoint,
+			_session_options: session_options
+		};
+		trainer.train_input_names = trainer.io_names(api.TrainingSessionGetTrainingModelInputCount, api.TrainingSessionGetTrainingModelInputName)?;
+		trainer.train_output_names = trainer.io_names(api.TrainingSessionGetTrainingModelOutputCount, api.TrainingSessionGetTrainingModelOutputName)?;
+		trainer.eval_input_names = trainer.io_names(api.TrainingSessionGetEvalModelInputCount, api.TrainingSessionGetEvalModelInputName)?;
+		trainer.eval_output_names = trainer.io_names(api.TrainingSessionGetEvalModelOutputCount, api.TrainingSessionGetEvalModelOutputName)?;
+		Ok(trainer)
+	}
+
+	fn io_names(
+		&self,
+		count_fn: Option<crate::extern_system_fn! { unsafe fn(*const ort_sys::OrtTrainingSession, *mut ort_sys::size_t) -> ort_sys::OrtStatusPtr }>,
+		name_fn: Option<
+			crate::extern_system_fn! { unsafe fn(*const ort_sys::OrtTrainingSession, ort_sys::size_t, *mut ort_sys::OrtAllocator, *mut *mut c_char) -> ort_sys::OrtStatusPtr }
+		>
+	) -> Result<Vec<String>> {
+		let allocator = Allocator::default();
+
+		let mut count = 0;
+		status_to_result(unsafe { count_fn.unwrap()(self.ptr, &mut count) }).map_err(Error::GetInOutCount)?;
+		(0..count)
+			.map(|i| {
+				let mut name: *mut c_char = ptr::null_mut();
+				status_to_result(unsafe { name_fn.unwrap()(self.ptr, i, allocator.ptr, &mut name) }).map_err(Error::GetInputName)?;
+				assert_non_null_pointer(name, "InputName")?;
+				raw_pointer_to_string(allocator.ptr, name)
+			})
+			.collect()
+	}
+
+	/// Returns the names of the inputs of the training model, in the order expected by [`Trainer::train_step`].
+	pub fn train_input_names(&self) -> &[String] {
+		&self.train_input_names
+	}
+
+	/// Returns the names of the outputs of the training model, e.g. the loss.
+	pub fn train_output_names(&self) -> &[String] {
+		&self.train_output_names
+	}
+
+	/// Returns the names of the inputs of the eval model, in the order expected by [`Trainer::eval_step`].
+	pub fn eval_input_names(&self) -> &[String] {
+		&self.eval_input_names
+	}
+
+	/// Returns the names of the outputs of the eval model.
+	pub fn eval_output_names(&self) -> &[String] {
+		&self.eval_output_names
+	}
+
+	/// Returns the checkpoint holding the state of the model being trained, which can be
+	/// [saved](Checkpoint::save) to resume training later.
+	pub fn checkpoint(&self) -> &Checkpoint {
+		&self.checkpoint
+	}
+
+	/// Runs the training model on a batch of inputs, computing the outputs (e.g. the loss) and accumulating the
+	/// gradients of the model's parameters. The parameters are only updated by [`Trainer::optimizer_step`].
+	pub fn train_step<'s, 'i, const N: usize>(&'s self, inputs: impl Into<SessionInputs<'i, N>>) -> Result<SessionOutputs<'s>> {
+		let inputs = inputs.into();
+		let input_ptrs = ordered_inputs(&self.train_input_names, &inputs)?;
+		let mut output_ptrs: Vec<*mut ort_sys::OrtValue> = vec![ptr::null_mut(); self.train_output_names.len()];
+		trainsys![
+			unsafe TrainStep(self.ptr, ptr::null(), input_ptrs.len() as _, input_ptrs.as_ptr(), output_ptrs.len() as _, output_ptrs.as_mut_ptr())
+				-> Error::TrainStep
+		];
+		Ok(outputs(&self.train_output_names, output_ptrs))
+	}
+
+	/// Runs the eval model on a batch of inputs, computing its outputs without accumulating gradients.
+	pub fn eval_step<'s, 'i, const N: usize>(&'s self, inputs: impl Into<SessionInputs<'i, N>>) -> Result<SessionOutputs<'s>> {
+		let inputs = inputs.into();
+		let input_ptrs = ordered_inputs(&self.eval_input_names, &inputs)?;
+		let mut output_ptrs: Vec<*mut ort_sys::OrtValue> = vec![ptr::null_mut(); self.eval_output_names.len()];
+		trainsys![
+			unsafe EvalStep(self.ptr, ptr::null(), input_ptrs.len() as _, input_ptrs.as_ptr(), output_ptrs.len() as _, output_ptrs.as_mut_ptr())
+				-> Error::EvalStep
+		];
+		Ok(outputs(&self.eval_output_names, output_ptrs))
+	}
+
+	/// Updates the parameters of the model with the optimizer, using the gradients accumulated by previous training
+	/// steps.
+	pub fn optimizer_step(&self) -> Result<()> {
+		trainsys![unsafe OptimizerStep(self.ptr, ptr::null()) -> Error::OptimizerStep];
+		Ok(())
+	}
+
+	/// Resets the accumulated gradients of the model's parameters to zero. The gradients are reset lazily, at the start
+	/// of the next training step.
+	pub fn lazy_reset_grad(&self) -> Result<()> {
+		trainsys![unsafe LazyResetGrad(self.ptr) -> Error::LazyResetGrad];
+		Ok(())
+	}
+
+	/// Sets the learning rate used by the optimizer.
+	pub fn set_learning_rate(&self, learning_rate: f32) -> Result<()> {
+		trainsys![unsafe SetLearningRate(self.ptr, learning_rate) -> Error::SetLearningRate];
+		Ok(())
+	}
+
+	/// Returns the learning rate currently used by the optimizer.
+	pub fn learning_rate(&self) -> Result<f32> {
+		let mut learning_rate = 0.0;
+		trainsys![unsafe GetLearningRate(self.ptr, &mut learning_rate) -> Error::GetLearningRate];
+		Ok(learning_rate)
+	}
+
+	/// Registers a linear learning rate scheduler, which increases the learning rate linearly from 0 to `initial_lr`
+	/// over `warmup_step_count` steps, then decreases it linearly to 0 by `total_step_count` steps. The scheduler is
+	/// advanced by [`Trainer::scheduler_step`].
+	pub fn register_linear_lr_scheduler(&self, warmup_step_count: i64, total_step_count: i64, initial_lr: f32) -> Result<()> {
+		trainsys![unsafe RegisterLinearLRScheduler(self.ptr, warmup_step_count, total_step_count, initial_lr) -> Error::RegisterLearningRateScheduler];
+		Ok(())
+	}
+
+	/// Advances the registered learning rate scheduler by one step, updating the learning rate of the optimizer.
+	pub fn scheduler_step(&self) -> Result<()> {
+		trainsys![unsafe SchedulerStep(self.ptr) -> Error::SchedulerStep];
+		Ok(())
+	}
+
+	/// Exports the eval model with the trained parameters to `path` as an inference-only model, with the given graph
+	/// outputs. The exported model can be loaded with [`SessionBuilder::with_model_from_file`].
+	pub fn export_model_for_inference(&self, path: impl AsRef<Path>, output_names: &[&str]) -> Result<()> {
+		let path = path_to_ortchar(path.as_ref());
+		let output_names = output_names.iter().map(|name| CString::new(*name)).collect::<Result<Vec<_>, _>>()?;
+		let output_name_ptrs: Vec<*const c_char> = output_names.iter().map(|name| name.as_ptr()).collect();
+		trainsys![
+			unsafe ExportModelForInferencing(self.ptr, path.as_ptr(), output_name_ptrs.len() as _, output_name_ptrs.as_ptr()) -> Error::ExportModelForInference
+		];
+		Ok(())
+	}
+}
+
+impl Drop for Trainer {
+	fn drop(&mut self) {
+		trainsys![unsafe ReleaseTrainingSession(self.ptr)];
+	}
+}
+
+/// Converts the paths of the training, eval, and optimizer models for ONNX Runtime, checking that each one exists.
+fn model_paths(paths: [&Path; 3]) -> Result<Vec<Vec<ort_sys::ortchar>>> {
+	paths
+		.into_iter()
+		.map(|path| {
+			if !path.exists() {
+				return Err(Error::FileDoesNotExist { filename: path.to_path_buf() });
+			}
+			Ok(path_to_ortchar(path))
+		})
+		.collect()
+}
+
+/// Returns pointers to the input values in the order of `names`, as the training API does not accept input names.
+fn ordered_inputs<const N: usize>(names: &[String], inputs: &SessionInputs<'_, N>) -> Result<Vec<*const ort_sys::OrtValue>> {
+	let values: Vec<&Value> = match inputs {
+		SessionInputs::ValueSlice(values) => values.iter().collect(),
+		SessionInputs::ValueArray(values) => values.iter().collect(),
+		SessionInputs::ValueMap(values) => {
+			if let Some(name) = values.keys().find(|name| !names.iter().any(|input| input == name.as_ref())) {
+				return Err(Error::UnknownInput(name.to_string()));
+			}
+			names
+				.iter()
+				.map(|name| values.get(name.as_str()).ok_or_else(|| Error::MissingInput(name.clone())))
+				.collect::<Result<_>>()?
+		}
+	};
+	if values.len() != names.len() {
+		return Err(Error::InputCountMismatch {
+			expected: names.len(),
+			actual: values.len()
+		});
+	}
+	Ok(values.into_iter().map(|value| value.ptr().cast_const()).collect())
+}
+
+fn outputs(names: &[String], output_ptrs: Vec<*mut ort_sys::OrtValue>) -> SessionOutputs<'_> {
+	// outputs are allocated by ONNX Runtime's default allocator, independent of the training session
+	let values = output_ptrs.into_iter().map(|ptr| unsafe { Value::from_detached_raw(ptr) });
+	SessionOutputs::new(names.iter().map(String::as_str), values)
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{collections::HashMap, sync::Arc};
+
+	use super::*;
+
+	fn value() -> Result<Value> {
+		Value::try_from((vec![1_i64], Arc::new(vec![0.0_f32].into_boxed_slice())))
+	}
+
+	fn names() -> Vec<String> {
+		vec!["input".to_string(), "labels".to_string()]
+	}
+
+	#[test]
+	fn test_model_paths() {
+		let data = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data");
+		let model = data.join("upsample.onnx");
+		let missing = data.join("eval_model.onnx");
+		assert_eq!(model_paths([&model, &model, &model]).map(|paths| paths.len()).ok(), Some(3));
+		assert!(matches!(model_paths([&model, &missing, &model]), Err(Error::FileDoesNotExist { filename }) if filename == missing));
+	}
+
+	#[test]
+	fn test_ordered_inputs() -> Result<()> {
+		let inputs = SessionInputs::from(HashMap::from([("labels", value()?), ("input", value()?)]));
+		let ptrs = ordered_inputs(&names(), &inputs)?;
+		let SessionInputs::ValueMap(values) = &inputs else {
+			unreachable!()
+		};
+		assert_eq!(ptrs, [values["input"].ptr().cast_const(), values["labels"].ptr().cast_const()]);
+
+		let inputs = SessionInputs::from(HashMap::from([("input", value()?), ("label", value()?)]));
+		assert!(matches!(ordered_inputs(&names(), &inputs), Err(Error::UnknownInput(name)) if name == "label"));
+
+		let inputs = SessionInputs::from(HashMap::from([("input", value()?)]));
+		assert!(matches!(ordered_inputs(&names(), &inputs), Err(Error::MissingInput(name)) if name == "labels"));
+
+		let inputs = SessionInputs::from([value()?]);
+		assert!(matches!(ordered_inputs(&names(), &inputs), Err(Error::InputCountMismatch { expected: 2, actual: 1 })));
+
+		Ok(())
+	}
+}
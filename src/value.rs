@@ -1,4 +1,4 @@
-use std::{any::Any, ffi, fmt::Debug, ops::Deref, ptr, sync::Arc};
+use std::{any::Any, collections::HashMap, ffi, fmt::Debug, hash::Hash, ops::Deref, ptr, sync::Arc};
 
 #[cfg(feature = "ndarray")]
 use ndarray::{ArcArray, Array, ArrayView, CowArray, Dimension, IxDyn};
@@ -9,23 +9,96 @@ use crate::{
 	error::assert_non_null_pointer,
 	memory::{Allocator, MemoryInfo},
 	ortsys,
-	session::SharedSessionInner,
-	tensor::{ExtractTensorData, IntoTensorElementDataType, TensorElementDataType, Utf8Data},
+	session::{dangerous, SharedSessionInner},
+	tensor::{extract_strings, ExtractTensorData, IntoTensorElementDataType, TensorElementDataType, Utf8Data},
 	AllocatorType, Error, MemType, Result
 };
 
+/// A single dimension of a tensor's shape, as declared by the model.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum Dim {
+	/// A dimension with a fixed size.
+	Fixed(i64),
+	/// A dynamic dimension with a symbolic name, e.g. `batch` or `sequence`. Dimensions with the same name are expected
+	/// to be of the same size.
+	Symbolic(String),
+	/// A dynamic dimension with no name.
+	Unknown
+}
+
+impl Dim {
+	/// Returns the size of this dimension if it is fixed.
+	pub fn as_fixed(&self) -> Option<i64> {
+		match self {
+			Dim::Fixed(size) => Some(*size),
+			_ => None
+		}
+	}
+
+	/// Returns the symbolic name of this dimension if it has one.
+	pub fn symbol(&self) -> Option<&str> {
+		match self {
+			Dim::Symbolic(name) => Some(name),
+			_ => None
+		}
+	}
+
+	/// Returns `true` if this dimension can be of any size.
+	pub fn is_dynamic(&self) -> bool {
+		!matches!(self, Dim::Fixed(_))
+	}
+}
+
+/// The type of a model input or output.
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum ValueType {
-	Tensor { ty: TensorElementDataType, dimensions: Vec<i64> },
+	/// A tensor of elements of type `ty`, with the given dimensions. Dynamic dimensions are represented as `-1`; the
+	/// symbolic name of each dynamic dimension, if it has one, is in `dimension_symbols`.
+	Tensor {
+		ty: TensorElementDataType,
+		dimensions: Vec<i64>,
+		dimension_symbols: Vec<Option<String>>
+	},
+	/// A sparse tensor of elements of type `ty`, with the given dense dimensions. See
+	/// [`SparseTensor`](crate::SparseTensor).
+	SparseTensor {
+		ty: TensorElementDataType,
+		dimensions: Vec<i64>,
+		dimension_symbols: Vec<Option<String>>
+	},
+	/// A sequence of values of the contained type, which may itself be a sequence.
 	Sequence(Box<ValueType>),
-	Map { key: TensorElementDataType, value: TensorElementDataType }
+	/// A map from keys of type `key` to values of type `value`.
+	Map { key: TensorElementDataType, value: Box<ValueType> },
+	/// A value of the contained type which may be absent.
+	Optional(Box<ValueType>)
 }
 
 impl ValueType {
-	/// Returns the dimensions of this data type if it is a tensor, or `None` if it is a sequence or map.
+	/// Returns the dimensions of this data type if it is a tensor, or `None` if it is a sequence or map. Dynamic
+	/// dimensions are represented as `-1`; use [`ValueType::tensor_shape`] to get their symbolic names.
 	pub fn tensor_dimensions(&self) -> Option<&Vec<i64>> {
 		match self {
-			ValueType::Tensor { dimensions, .. } => Some(dimensions),
+			ValueType::Tensor { dimensions, .. } | ValueType::SparseTensor { dimensions, .. } => Some(dimensions),
+			_ => None
+		}
+	}
+
+	/// Returns the shape of this data type as [`Dim`]s if it is a dense or sparse tensor, or `None` if it is a
+	/// sequence, map, or optional.
+	pub fn tensor_shape(&self) -> Option<Vec<Dim>> {
+		match self {
+			ValueType::Tensor { dimensions, dimension_symbols, .. } | ValueType::SparseTensor { dimensions, dimension_symbols, .. } => Some(
+				dimensions
+					.iter()
+					.zip(dimension_symbols)
+					.map(|(&size, symbol)| match symbol {
+						_ if size >= 0 => Dim::Fixed(size),
+						Some(symbol) => Dim::Symbolic(symbol.clone()),
+						None => Dim::Unknown
+					})
+					.collect()
+			),
 			_ => None
 		}
 	}
@@ -112,17 +185,26 @@ pub(crate) enum ValueInner {
 		ptr: *mut ort_sys::OrtValue,
 		/// Hold [`SharedSessionInner`] to ensure that the value can stay alive after the main session is dropped.
 		_session: Arc<SharedSessionInner>
-	}
+	},
+	/// A value created by ONNX Runtime with the default allocator, independent of any session, e.g. an element
+	/// extracted from a sequence.
+	Detached { ptr: *mut ort_sys::OrtValue },
+	/// A sequence or map created from other [`Value`]s, which are kept alive for as long as the container is.
+	Container { ptr: *mut ort_sys::OrtValue, _values: Vec<Value> }
 }
 
-/// A [`Value`] contains data for inputs/outputs in ONNX Runtime graphs. [`Value`]s can hold a tensor, sequence (array),
-/// or map.
+/// A [`Value`] contains data for inputs/outputs in ONNX Runtime graphs. [`Value`]s can hold a tensor, sparse tensor,
+/// sequence (array), or map.
 #[derive(Debug)]
 pub struct Value {
 	inner: ValueInner
 }
 
 unsafe impl Send for Value {}
+// Shared references only ever read a value's data: ONNX Runtime only writes into a value owned by the run (a
+// pre-allocated output) or passed to it through a `&mut` reference (a bound output), and the `Box<dyn Any>` owner of
+// Rust-owned data is never accessed.
+unsafe impl Sync for Value {}
 
 impl Value {
 	/// Construct a [`Value`] from a C++ [`ort_sys::OrtValue`] pointer.
@@ -136,6 +218,56 @@ impl Value {
 		}
 	}
 
+	/// Construct a [`Value`] from a [`ort_sys::OrtValue`] pointer allocated with the default allocator, which does not
+	/// depend on any session.
+	///
+	/// # Safety
+	///
+	/// - `ptr` must not be null, and must not be released elsewhere.
+	pub(crate) unsafe fn from_detached_raw(ptr: *mut ort_sys::OrtValue) -> Value {
+		Value { inner: ValueInner::Detached { ptr } }
+	}
+
+	/// Creates a CPU tensor [`Value`] which references `len` bytes of existing data at `data` without copying it.
+	///
+	/// # Safety
+	///
+	/// - `data` must point to `len` bytes laid out as a tensor of type `ty` & shape `shape`, which stay valid &
+	///   unmodified for as long as `owner` is alive.
+	pub(crate) unsafe fn from_raw_tensor_data(data: *const u8, len: usize, shape: &[i64], ty: TensorElementDataType, owner: Box<dyn Any>) -> Result<Value> {
+		let memory_info = MemoryInfo::new_cpu(AllocatorType::Arena, MemType::Default)?;
+		let mut value_ptr: *mut ort_sys::OrtValue = ptr::null_mut();
+		ortsys![
+			unsafe CreateTensorWithDataAsOrtValue(memory_info.ptr, data as *mut _, len as _, shape.as_ptr(), shape.len() as _, ty.into(), &mut value_ptr)
+				-> Error::CreateTensorWithData;
+			nonNull(value_ptr)
+		];
+		Ok(Value {
+			inner: ValueInner::RustOwned {
+				ptr: value_ptr,
+				_array: owner,
+				_memory_info: memory_info
+			}
+		})
+	}
+
+	/// Returns the shape of this value if it is a tensor.
+	pub fn shape(&self) -> Result<Vec<i64>> {
+		let mut tensor_info_ptr: *mut ort_sys::OrtTensorTypeAndShapeInfo = std::ptr::null_mut();
+		ortsys![unsafe GetTensorTypeAndShape(self.ptr(), &mut tensor_info_ptr) -> Error::GetTensorTypeAndShape];
+
+		let res = {
+			let mut num_dims = 0;
+			ortsys![unsafe GetDimensionsCount(tensor_info_ptr, &mut num_dims) -> Error::GetDimensionsCount];
+
+			let mut node_dims: Vec<i64> = vec![0; num_dims as _];
+			ortsys![unsafe GetDimensions(tensor_info_ptr, node_dims.as_mut_ptr(), num_dims as _) -> Error::GetDimensions];
+			Ok(node_dims)
+		};
+		ortsys![unsafe ReleaseTensorTypeAndShapeInfo(tensor_info_ptr)];
+		res
+	}
+
 	pub fn dtype(&self) -> Result<TensorElementDataType> {
 		let mut tensor_info_ptr: *mut ort_sys::OrtTensorTypeAndShapeInfo = std::ptr::null_mut();
 		ortsys![unsafe GetTensorTypeAndShape(self.ptr(), &mut tensor_info_ptr) -> Error::GetTensorTypeAndShape];
@@ -386,7 +518,9 @@ impl Value {
 	pub(crate) fn ptr(&self) -> *mut ort_sys::OrtValue {
 		match &self.inner {
 			ValueInner::CppOwned { ptr, .. } => *ptr,
-			ValueInner::RustOwned { ptr, .. } => *ptr
+			ValueInner::RustOwned { ptr, .. } => *ptr,
+			ValueInner::Detached { ptr } => *ptr,
+			ValueInner::Container { ptr, .. } => *ptr
 		}
 	}
 
@@ -396,8 +530,177 @@ impl Value {
 		ortsys![unsafe IsTensor(self.ptr(), &mut result) -> Error::GetTensorElementType];
 		Ok(result == 1)
 	}
+
+	/// Returns the full type of this value, including the element types of sequences & maps.
+	pub(crate) fn value_type(&self) -> Result<ValueType> {
+		let mut typeinfo_ptr: *mut ort_sys::OrtTypeInfo = ptr::null_mut();
+		ortsys![unsafe GetTypeInfo(self.ptr(), &mut typeinfo_ptr) -> Error::GetTypeInfo; nonNull(typeinfo_ptr)];
+		unsafe { dangerous::extract_owned_data_type(typeinfo_ptr) }
+	}
+
+	pub(crate) fn onnx_type(&self) -> Result<ort_sys::ONNXType> {
+		let mut ty = ort_sys::ONNXType::ONNX_TYPE_UNKNOWN;
+		ortsys![unsafe GetValueType(self.ptr(), &mut ty) -> Error::GetValueType];
+		Ok(ty)
+	}
+
+	fn expect_onnx_type(&self, expected: ort_sys::ONNXType) -> Result<()> {
+		let actual = self.onnx_type()?;
+		if actual != expected {
+			return Err(Error::ValueTypeMismatch { expected, actual });
+		}
+		Ok(())
+	}
+
+	/// Construct a sequence [`Value`] from a list of values, which must all be tensors of the same element type, or all
+	/// be maps of the same type.
+	///
+	/// ```no_run
+	/// # use ort::Value;
+	/// # fn main() -> ort::Result<()> {
+	/// let sequence = Value::from_sequence(vec![
+	/// 	Value::from_array(ndarray::Array1::from_vec(vec![1.0_f32, 2.0]))?,
+	/// 	Value::from_array(ndarray::Array1::from_vec(vec![3.0_f32]))?,
+	/// ])?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn from_sequence(values: Vec<Value>) -> Result<Value> {
+		let value_ptrs: Vec<*const ort_sys::OrtValue> = values.iter().map(|value| value.ptr() as *const _).collect();
+		let mut value_ptr: *mut ort_sys::OrtValue = ptr::null_mut();
+		ortsys![
+			unsafe CreateValue(value_ptrs.as_ptr(), value_ptrs.len() as _, ort_sys::ONNXType::ONNX_TYPE_SEQUENCE, &mut value_ptr) -> Error::CreateValue;
+			nonNull(value_ptr)
+		];
+		Ok(Value {
+			inner: ValueInner::Container { ptr: value_ptr, _values: values }
+		})
+	}
+
+	/// Returns the number of elements in this value if it is a sequence or map.
+	fn value_count(&self) -> Result<usize> {
+		let mut count: ort_sys::size_t = 0;
+		ortsys![unsafe GetValueCount(self.ptr(), &mut count) -> Error::GetValueCount];
+		Ok(count as _)
+	}
+
+	/// Returns the element at `index` of this sequence or map. The element is a copy allocated by ONNX Runtime, so it
+	/// can outlive this value.
+	fn get_value(&self, index: usize) -> Result<Value> {
+		let allocator = Allocator::default();
+		let mut value_ptr: *mut ort_sys::OrtValue = ptr::null_mut();
+		ortsys![unsafe GetValue(self.ptr(), index as _, allocator.ptr, &mut value_ptr) -> Error::GetValue; nonNull(value_ptr)];
+		Ok(unsafe { Value::from_detached_raw(value_ptr) })
+	}
+
+	/// Extracts the elements of a sequence value, e.g. the per-sample maps returned by sklearn-onnx classifiers.
+	///
+	/// Returns [`Error::ValueTypeMismatch`] if this value is not a sequence.
+	pub fn extract_sequence(&self) -> Result<Vec<Value>> {
+		self.expect_onnx_type(ort_sys::ONNXType::ONNX_TYPE_SEQUENCE)?;
+		(0..self.value_count()?).map(|i| self.get_value(i)).collect()
+	}
+
+	/// Construct a map [`Value`] from a `HashMap`. See [`MapKey`] and [`MapValue`] for the supported key & value types.
+	///
+	/// ```no_run
+	/// # use std::collections::HashMap;
+	/// # use ort::Value;
+	/// # fn main() -> ort::Result<()> {
+	/// let features = Value::from_map(HashMap::from([("height".to_string(), 1.8_f32), ("weight".to_string(), 75.0)]))?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn from_map<K: MapKey, V: MapValue>(map: HashMap<K, V>) -> Result<Value> {
+		let (keys, values): (Vec<K>, Vec<V>) = map.into_iter().unzip();
+		let keys = K::into_tensor(keys)?;
+		let values = V::into_tensor(values)?;
+
+		let value_ptrs: [*const ort_sys::OrtValue; 2] = [keys.ptr(), values.ptr()];
+		let mut value_ptr: *mut ort_sys::OrtValue = ptr::null_mut();
+		ortsys![
+			unsafe CreateValue(value_ptrs.as_ptr(), value_ptrs.len() as _, ort_sys::ONNXType::ONNX_TYPE_MAP, &mut value_ptr) -> Error::CreateValue;
+			nonNull(value_ptr)
+		];
+		Ok(Value {
+			inner: ValueInner::Container {
+				ptr: value_ptr,
+				_values: vec![keys, values]
+			}
+		})
+	}
+
+	/// Extracts the contents of a map value into a `HashMap`.
+	///
+	/// Returns [`Error::ValueTypeMismatch`] if this value is not a map, or [`Error::DataTypeMismatch`] if `K` or `V`
+	/// do not match the map's key or value type.
+	pub fn extract_map<K: MapKey, V: MapValue>(&self) -> Result<HashMap<K, V>> {
+		self.expect_onnx_type(ort_sys::ONNXType::ONNX_TYPE_MAP)?;
+		// a map is made up of a tensor of keys at index 0 and a tensor of values at index 1
+		let keys = K::from_tensor(&self.get_value(0)?)?;
+		let values = V::from_tensor(&self.get_value(1)?)?;
+		Ok(keys.into_iter().zip(values).collect())
+	}
+}
+
+/// A type which can be the element of a map [`Value`]'s keys or values tensor.
+pub trait MapElement: Sized {
+	/// Creates a 1-dimensional tensor from `elements`.
+	fn into_tensor(elements: Vec<Self>) -> Result<Value>;
+
+	/// Extracts the elements of a 1-dimensional tensor.
+	fn from_tensor(value: &Value) -> Result<Vec<Self>>;
+}
+
+/// A type which can be the key of a map [`Value`]. ONNX allows `i64` and `String` keys.
+pub trait MapKey: MapElement + Eq + Hash {}
+
+/// A type which can be the value of a map [`Value`]. ONNX allows `i64`, `f32`, `f64`, and `String` values.
+pub trait MapValue: MapElement {}
+
+macro_rules! impl_map_element {
+	($type_:ty) => {
+		impl MapElement for $type_ {
+			fn into_tensor(elements: Vec<Self>) -> Result<Value> {
+				Value::from_array((vec![elements.len() as i64], Arc::new(elements.into_boxed_slice())))
+			}
+
+			fn from_tensor(value: &Value) -> Result<Vec<Self>> {
+				Ok(value.extract_raw_tensor::<$type_>()?.1.to_vec())
+			}
+		}
+	};
+}
+
+impl_map_element!(i64);
+impl_map_element!(f32);
+impl_map_element!(f64);
+
+impl MapElement for String {
+	fn into_tensor(elements: Vec<Self>) -> Result<Value> {
+		Value::from_string_array(&Allocator::default(), (vec![elements.len() as i64], Arc::new(elements.into_boxed_slice())))
+	}
+
+	fn from_tensor(value: &Value) -> Result<Vec<Self>> {
+		let data_type = value.dtype()?;
+		if data_type != TensorElementDataType::String {
+			return Err(Error::DataTypeMismatch {
+				actual: data_type,
+				requested: TensorElementDataType::String
+			});
+		}
+		let len = value.shape()?.iter().product::<i64>();
+		extract_strings(len as _, value.ptr())
+	}
 }
 
+impl MapKey for i64 {}
+impl MapKey for String {}
+impl MapValue for i64 {}
+impl MapValue for f32 {}
+impl MapValue for f64 {}
+impl MapValue for String {}
+
 #[cfg(feature = "ndarray")]
 #[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
 impl<'i, 'v, T: Clone + 'static, D: Dimension + 'static> OrtInput for &'i CowArray<'v, T, D>
@@ -563,6 +866,15 @@ impl<'v, T: IntoTensorElementDataType + Debug + Clone + 'static, D: Dimension +
 	}
 }
 
+#[cfg(feature = "ndarray")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
+impl<T: ExtractTensorData + Clone + Debug> TryFrom<Value> for ndarray::ArrayD<T> {
+	type Error = Error;
+	fn try_from(value: Value) -> Result<Self, Self::Error> {
+		Ok(value.extract_tensor::<T>()?.view().to_owned())
+	}
+}
+
 impl<T: IntoTensorElementDataType + Debug + Clone + 'static> TryFrom<(Vec<i64>, Arc<Box<[T]>>)> for Value {
 	type Error = Error;
 	fn try_from(d: (Vec<i64>, Arc<Box<[T]>>)) -> Result<Self, Self::Error> {
@@ -578,7 +890,9 @@ impl Drop for Value {
 			"dropping {} value at {ptr:p}",
 			match &self.inner {
 				ValueInner::RustOwned { .. } => "rust-owned",
-				ValueInner::CppOwned { .. } => "cpp-owned"
+				ValueInner::CppOwned { .. } => "cpp-owned",
+				ValueInner::Detached { .. } => "detached",
+				ValueInner::Container { .. } => "container"
 			}
 		);
 		ortsys![unsafe ReleaseValue(ptr)];
@@ -0,0 +1,83 @@
+#![cfg(feature = "custom-ops")]
+
+use std::path::Path;
+
+use ndarray::array;
+use ort::{inputs, CustomOp, KernelContext, OperatorDomain, OperatorInput, OperatorOutput, Session, TensorElementDataType};
+use test_log::test;
+
+/// Computes `x * factor + offset`, where `factor` & `offset` are attributes of the node.
+struct ScaleOffset;
+
+impl CustomOp for ScaleOffset {
+	fn name(&self) -> &str {
+		"ScaleOffset"
+	}
+
+	fn inputs(&self) -> Vec<OperatorInput> {
+		vec![OperatorInput::required(TensorElementDataType::Float32)]
+	}
+
+	fn outputs(&self) -> Vec<OperatorOutput> {
+		vec![OperatorOutput::required(TensorElementDataType::Float32)]
+	}
+
+	fn compute(&self, ctx: &KernelContext<'_>) -> ort::Result<()> {
+		let factor: f32 = ctx.attributes().get("factor")?;
+		let offset: i64 = ctx.attributes().get("offset")?;
+		assert!(ctx.attributes().get::<String>("factor").is_err());
+
+		let x = ctx.input::<f32>(0)?.expect("input is required");
+		let mut y = ctx.output::<f32>(0, x.shape())?.expect("output is required");
+		y.assign(&x.mapv(|x| x * factor + offset as f32));
+		assert!(matches!(ctx.output::<f32>(0, x.shape()), Err(ort::Error::KernelOutputAlreadyAllocated(0))));
+		Ok(())
+	}
+}
+
+/// Like [`ScaleOffset`], but fails to compute.
+struct Failing;
+
+impl CustomOp for Failing {
+	fn name(&self) -> &str {
+		"ScaleOffset"
+	}
+
+	fn inputs(&self) -> Vec<OperatorInput> {
+		vec![OperatorInput::required(TensorElementDataType::Float32)]
+	}
+
+	fn outputs(&self) -> Vec<OperatorOutput> {
+		vec![OperatorOutput::required(TensorElementDataType::Float32)]
+	}
+
+	fn compute(&self, ctx: &KernelContext<'_>) -> ort::Result<()> {
+		ctx.input::<i64>(0)?;
+		Ok(())
+	}
+}
+
+fn model_path() -> std::path::PathBuf {
+	// `custom_op.onnx` contains a single `test.ops.ScaleOffset` node with `factor = 3.0` & `offset = 1`.
+	Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("custom_op.onnx")
+}
+
+#[test]
+fn custom_operator() -> ort::Result<()> {
+	let session = Session::builder()?
+		.with_operators(OperatorDomain::new("test.ops")?.with_operator(ScaleOffset)?)?
+		.with_model_from_file(model_path())?;
+	let outputs = session.run(inputs!["x" => array![1.0_f32, 2.0, -1.0]]?)?;
+	assert_eq!(outputs["y"].extract_raw_tensor::<f32>()?.1, &[4.0, 7.0, -2.0]);
+
+	// errors in `compute` fail the run instead of aborting
+	let session = Session::builder()?
+		.with_operators(OperatorDomain::new("test.ops")?.with_operator(Failing)?)?
+		.with_model_from_file(model_path())?;
+	assert!(session.run(inputs!["x" => array![1.0_f32]]?).is_err());
+
+	// the model cannot be loaded without its operators
+	assert!(Session::builder()?.with_model_from_file(model_path()).is_err());
+
+	Ok(())
+}
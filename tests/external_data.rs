@@ -0,0 +1,60 @@
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use ndarray::array;
+use ort::{inputs, Session};
+use test_log::test;
+
+fn data_dir() -> PathBuf {
+	// `external_data.onnx` adds `w` (`[1.0, 2.0, 3.0]`, stored at offset 8 of `external_data.bin`) to `x`.
+	PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join("data")
+}
+
+#[test]
+fn external_data_from_memory() -> ort::Result<()> {
+	let model = fs::read(data_dir().join("external_data.onnx")).expect("failed to read model");
+	let weights = fs::read(data_dir().join("external_data.bin")).expect("failed to read external data");
+
+	let session = Session::builder()?.with_model_from_memory_and_external_data(&model, HashMap::from([("external_data.bin".to_string(), weights.clone())]))?;
+	let outputs = session.run(inputs!["x" => array![1.0_f32, 1.0, 1.0]]?)?;
+	assert_eq!(outputs["y"].extract_raw_tensor::<f32>()?.1, &[2.0, 3.0, 4.0]);
+
+	assert!(matches!(
+		Session::builder()?.with_model_from_memory_and_external_data(&model, HashMap::from([("weights.bin".to_string(), weights.clone())])),
+		Err(ort::Error::MissingExternalData(location)) if location == "external_data.bin"
+	));
+	assert!(matches!(
+		Session::builder()?.with_model_from_memory_and_external_data(&model, HashMap::from([("external_data.bin".to_string(), weights[..16].to_vec())])),
+		Err(ort::Error::ExternalDataOutOfBounds(name)) if name == "w"
+	));
+
+	Ok(())
+}
+
+#[test]
+#[cfg(feature = "mmap")]
+fn external_data_from_mapped_file() -> ort::Result<()> {
+	let model = fs::read(data_dir().join("external_data.onnx")).expect("failed to read model");
+	let file = fs::File::open(data_dir().join("external_data.bin")).expect("failed to open external data");
+	let weights = unsafe { memmap2::Mmap::map(&file) }.expect("failed to map external data");
+
+	let session = Session::builder()?.with_model_from_memory_and_external_data(&model, HashMap::from([("external_data.bin".to_string(), weights)]))?;
+	let outputs = session.run(inputs!["x" => array![0.0_f32, 0.0, 0.0]]?)?;
+	assert_eq!(outputs["y"].extract_raw_tensor::<f32>()?.1, &[1.0, 2.0, 3.0]);
+
+	Ok(())
+}
+
+#[test]
+fn external_data_unaligned() -> ort::Result<()> {
+	// `external_data_unaligned.onnx` is `external_data.onnx` with `w` stored at offset 9 of `unaligned.bin`, which does
+	// not align it for `f32`
+	let model = fs::read(data_dir().join("external_data_unaligned.onnx")).expect("failed to read model");
+	let mut weights = vec![0_u8; 9];
+	weights.extend([1.0_f32, 2.0, 3.0].iter().flat_map(|x| x.to_le_bytes()));
+
+	let session = Session::builder()?.with_model_from_memory_and_external_data(&model, HashMap::from([("unaligned.bin".to_string(), weights)]))?;
+	let outputs = session.run(inputs!["x" => array![1.0_f32, 1.0, 1.0]]?)?;
+	assert_eq!(outputs["y"].extract_raw_tensor::<f32>()?.1, &[2.0, 3.0, 4.0]);
+
+	Ok(())
+}
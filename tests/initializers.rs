@@ -0,0 +1,73 @@
+use std::{collections::HashMap, path::Path, sync::Arc};
+
+use ndarray::array;
+use ort::{inputs, Session, Value};
+use test_log::test;
+
+fn model_path() -> std::path::PathBuf {
+	// `overridable_initializer.onnx` multiplies `x` by `scale`, an initializer (defaulting to `[2.0]`) which is also
+	// listed as a graph input and can thus be overridden.
+	Path::new(env!("CARGO_MANIFEST_DIR"))
+		.join("tests")
+		.join("data")
+		.join("overridable_initializer.onnx")
+}
+
+#[test]
+fn overridable_initializers() -> ort::Result<()> {
+	let session = Session::builder()?.with_intra_threads(1)?.with_model_from_file(model_path())?;
+	assert_eq!(session.inputs.iter().map(|input| input.name.as_str()).collect::<Vec<_>>(), ["x"]);
+	assert_eq!(session.overridable_initializers.len(), 1);
+	assert_eq!(session.overridable_initializers[0].name, "scale");
+	assert_eq!(session.overridable_initializers[0].input_type.tensor_dimensions(), Some(&vec![1]));
+
+	let outputs = session.run(inputs!["x" => array![1.0_f32, 2.0]]?)?;
+	assert_eq!(outputs["y"].extract_raw_tensor::<f32>()?.1, &[2.0, 4.0]);
+
+	let outputs = session.run(inputs!["x" => array![1.0_f32, 2.0], "scale" => array![3.0_f32]]?)?;
+	assert_eq!(outputs["y"].extract_raw_tensor::<f32>()?.1, &[3.0, 6.0]);
+
+	// overriding initializers is validated like any other input
+	let inputs: HashMap<String, Value> =
+		HashMap::from([("x".to_string(), Value::from_array(array![1.0_f32])?), ("scale".to_string(), Value::from_array(array![3_i64])?)]);
+	assert!(matches!(session.run(inputs), Err(ort::Error::InputDataTypeMismatch { .. })));
+
+	Ok(())
+}
+
+#[test]
+fn session_initializers() -> ort::Result<()> {
+	// one weight buffer shared by several sessions
+	let scale = Arc::new(Value::from_array(array![5.0_f32])?);
+	let sessions = (0..2)
+		.map(|_| {
+			Session::builder()?
+				.with_initializer("scale", Arc::clone(&scale))?
+				.with_model_from_file(model_path())
+		})
+		.collect::<ort::Result<Vec<_>>>()?;
+	drop(scale);
+	for session in &sessions {
+		let outputs = session.run(inputs!["x" => array![1.0_f32, 2.0]]?)?;
+		assert_eq!(outputs["y"].extract_raw_tensor::<f32>()?.1, &[5.0, 10.0]);
+	}
+
+	// `external_data.onnx` adds `w`, an initializer stored in `external_data.bin`, to `x`
+	let session = Session::builder()?
+		.with_external_initializers(["w"], [Value::from_array(array![10.0_f32, 20.0, 30.0])?])?
+		.with_model_from_file(
+			Path::new(env!("CARGO_MANIFEST_DIR"))
+				.join("tests")
+				.join("data")
+				.join("external_data.onnx")
+		)?;
+	let outputs = session.run(inputs!["x" => array![1.0_f32, 2.0, 3.0]]?)?;
+	assert_eq!(outputs["y"].extract_raw_tensor::<f32>()?.1, &[11.0, 22.0, 33.0]);
+
+	assert!(matches!(
+		Session::builder()?.with_external_initializers(["scale", "bias"], [Value::from_array(array![0.5_f32])?]),
+		Err(ort::Error::InitializerCountMismatch { names: 2, values: 1 })
+	));
+
+	Ok(())
+}
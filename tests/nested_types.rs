@@ -0,0 +1,147 @@
+use std::{collections::HashMap, path::Path};
+
+use ndarray::{array, Array1};
+use ort::{inputs, Dim, Session, TensorElementDataType, Value, ValueType};
+use test_log::test;
+
+fn load_session() -> ort::Result<Session> {
+	// `nested_types.onnx` zips a `[batch, 3]` tensor of class probabilities into a sequence of maps (as classifiers
+	// converted by skl2onnx do), and passes an optional tensor through an `Identity` node.
+	Session::builder()?
+		.with_intra_threads(1)?
+		.with_model_from_file(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("nested_types.onnx"))
+}
+
+#[test]
+fn nested_value_types() -> ort::Result<()> {
+	let session = load_session()?;
+
+	let batch = || Some("batch".to_owned());
+	assert_eq!(
+		session.inputs[0].input_type,
+		ValueType::Tensor {
+			ty: TensorElementDataType::Float32,
+			dimensions: vec![-1, 3],
+			dimension_symbols: vec![batch(), None]
+		}
+	);
+	assert_eq!(session.inputs[0].input_type.tensor_shape(), Some(vec![Dim::Symbolic("batch".to_owned()), Dim::Fixed(3)]));
+	assert_eq!(
+		session.inputs[1].input_type,
+		ValueType::Optional(Box::new(ValueType::Tensor {
+			ty: TensorElementDataType::Float32,
+			dimensions: vec![-1],
+			dimension_symbols: vec![batch()]
+		}))
+	);
+
+	match &session.outputs[0].output_type {
+		ValueType::Sequence(element) => match element.as_ref() {
+			ValueType::Map { key, value } => {
+				assert_eq!(*key, TensorElementDataType::Int64);
+				assert!(matches!(
+					value.as_ref(),
+					ValueType::Tensor {
+						ty: TensorElementDataType::Float32,
+						..
+					}
+				));
+			}
+			other => panic!("expected sequence of maps, got sequence of {other:?}")
+		},
+		other => panic!("expected sequence, got {other:?}")
+	}
+	assert_eq!(session.outputs[1].output_type, session.inputs[1].input_type);
+
+	Ok(())
+}
+
+#[test]
+fn sequence_round_trip() -> ort::Result<()> {
+	let sequence = Value::from_sequence(vec![Value::from_array(Array1::from_vec(vec![1.0_f32, 2.0]))?, Value::from_array(Array1::from_vec(vec![3.0_f32]))?])?;
+	assert!(!sequence.is_tensor()?);
+
+	let elements = sequence.extract_sequence()?;
+	assert_eq!(elements.len(), 2);
+	assert_eq!(elements[0].extract_raw_tensor::<f32>()?, (vec![2], &[1.0, 2.0][..]));
+	assert_eq!(elements[1].extract_raw_tensor::<f32>()?, (vec![1], &[3.0][..]));
+
+	// elements are copies, so they stay valid after the sequence is dropped
+	drop(sequence);
+	assert_eq!(elements[1].extract_raw_tensor::<f32>()?.1, &[3.0]);
+
+	assert!(matches!(elements[0].extract_sequence(), Err(ort::Error::ValueTypeMismatch { .. })));
+
+	Ok(())
+}
+
+#[test]
+fn extract_sequence_output() -> ort::Result<()> {
+	let session = load_session()?;
+
+	let outputs = session.run(inputs![
+		"x" => array![[0.1_f32, 0.2, 0.7], [0.5, 0.25, 0.25]],
+		"maybe" => Array1::from_vec(vec![1.0_f32, 2.0])
+	]?)?;
+	let probabilities = outputs["probabilities"].extract_sequence()?;
+	assert_eq!(probabilities.len(), 2);
+	for map in &probabilities {
+		assert!(!map.is_tensor()?);
+	}
+	assert_eq!(probabilities[1].extract_map::<i64, f32>()?, HashMap::from([(0, 0.5), (1, 0.25), (2, 0.25)]));
+
+	Ok(())
+}
+
+#[test]
+fn omitted_optional_input() -> ort::Result<()> {
+	let session = load_session()?;
+
+	// `maybe` is optional, so it can be left out entirely
+	let outputs = session.run(inputs!["x" => array![[0.1_f32, 0.2, 0.7]]]?)?;
+	assert_eq!(outputs["probabilities"].extract_sequence()?.len(), 1);
+
+	// ...but a provided value must still match the contained type
+	assert!(matches!(
+		session.run(inputs!["x" => array![[0.1_f32, 0.2, 0.7]], "maybe" => Array1::from_vec(vec![1_i64])]?),
+		Err(ort::Error::InputDataTypeMismatch { .. })
+	));
+
+	Ok(())
+}
+
+#[test]
+fn sequence_input_types() -> ort::Result<()> {
+	// `sequence_length.onnx` returns the number of tensors in a sequence of float tensors.
+	let session = Session::builder()?.with_intra_threads(1)?.with_model_from_file(
+		Path::new(env!("CARGO_MANIFEST_DIR"))
+			.join("tests")
+			.join("data")
+			.join("sequence_length.onnx")
+	)?;
+
+	let floats = Value::from_sequence(vec![Value::from_array(Array1::from_vec(vec![1.0_f32]))?, Value::from_array(Array1::from_vec(vec![2.0_f32, 3.0]))?])?;
+	let outputs = session.run(inputs!["sequence" => floats]?)?;
+	assert_eq!(outputs["length"].extract_raw_tensor::<i64>()?.1, &[2]);
+
+	let ints = Value::from_sequence(vec![Value::from_array(Array1::from_vec(vec![1_i64]))?])?;
+	assert!(matches!(session.run(inputs!["sequence" => ints]?), Err(ort::Error::InputTypeMismatch { .. })));
+	assert!(matches!(session.run(inputs!["sequence" => Array1::from_vec(vec![1.0_f32])]?), Err(ort::Error::InputTypeMismatch { .. })));
+
+	Ok(())
+}
+
+#[test]
+fn map_round_trip() -> ort::Result<()> {
+	let features = HashMap::from([("height".to_string(), 1.8_f32), ("weight".to_string(), 75.0)]);
+	let map = Value::from_map(features.clone())?;
+	assert!(!map.is_tensor()?);
+	assert_eq!(map.extract_map::<String, f32>()?, features);
+	assert!(matches!(map.extract_map::<String, f64>(), Err(ort::Error::DataTypeMismatch { .. })));
+	assert!(matches!(map.extract_sequence(), Err(ort::Error::ValueTypeMismatch { .. })));
+
+	let labels = HashMap::from([(0_i64, "cat".to_string()), (1, "dog".to_string())]);
+	assert_eq!(Value::from_map(labels.clone())?.extract_map::<i64, String>()?, labels);
+
+	Ok(())
+}
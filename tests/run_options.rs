@@ -0,0 +1,62 @@
+use std::path::Path;
+
+use ndarray::array;
+use ort::{inputs, LogLevel, OutputSelector, RunOptions, Session, Tensor};
+use test_log::test;
+
+fn multi_output_session() -> ort::Result<Session> {
+	ort::init().with_name("integration_test").commit()?;
+
+	// `multi_output.onnx` computes `double = x + x` and `square = x * x`
+	Session::builder()?
+		.with_intra_threads(1)?
+		.with_model_from_file(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("multi_output.onnx"))
+}
+
+#[test]
+fn output_selector_blocklist() -> ort::Result<()> {
+	let session = multi_output_session()?;
+
+	let mut options = RunOptions::new()?;
+	options.set_outputs(OutputSelector::default().without("double"));
+	let outputs = session.run_with_options(inputs![array![1.0_f32, 2.0, 3.0]]?, &options)?;
+	assert!(!outputs.contains_key("double"));
+	let square: Tensor<f32> = outputs["square"].extract_tensor()?;
+	assert_eq!(square.view().as_slice(), Some(&[1.0, 4.0, 9.0][..]));
+
+	Ok(())
+}
+
+#[test]
+fn output_selector_allowlist() -> ort::Result<()> {
+	let session = multi_output_session()?;
+
+	let mut options = RunOptions::new()?;
+	options.set_outputs(OutputSelector::no_default().with("double"));
+	let outputs = session.run_with_options(inputs![array![1.0_f32, 2.0, 3.0]]?, &options)?;
+	assert_eq!(outputs.len(), 1);
+	assert!(!outputs.contains_key("square"));
+	let double: Tensor<f32> = outputs["double"].extract_tensor()?;
+	assert_eq!(double.view().as_slice(), Some(&[2.0, 4.0, 6.0][..]));
+
+	Ok(())
+}
+
+#[test]
+fn run_options_configuration() -> ort::Result<()> {
+	let session = multi_output_session()?;
+
+	let mut options = RunOptions::new()?;
+	options.set_tag("configured-run")?;
+	assert_eq!(options.tag()?, "configured-run");
+	options.set_log_level(LogLevel::Warning)?;
+	options.add_config_entry("memory.enable_memory_arena_shrinkage", "cpu:0")?;
+	let outputs = session.run_with_options(inputs![array![1.0_f32, 2.0, 3.0]]?, &options)?;
+	let square: Tensor<f32> = outputs["square"].extract_tensor()?;
+	assert_eq!(square.view().as_slice(), Some(&[1.0, 4.0, 9.0][..]));
+
+	// ONNX Runtime rejects empty configuration keys
+	assert!(matches!(options.add_config_entry("", "1"), Err(ort::Error::RunOptionsAddConfigEntry(_))));
+
+	Ok(())
+}
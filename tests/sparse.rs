@@ -0,0 +1,89 @@
+use std::path::Path;
+
+use ndarray::array;
+use ort::{inputs, Dim, Session, SparseFormat, SparseIndices, SparseTensor, TensorElementDataType, Value, ValueType};
+use test_log::test;
+
+#[test]
+fn sparse_round_trip() -> ort::Result<()> {
+	// [[0, 1, 0], [2, 0, 0]]
+	let values = [1.0_f32, 2.0];
+
+	let coo = Value::from_sparse_tensor(&SparseTensor::coo([2, 3], &values, &[1, 3])?)?;
+	assert!(coo.is_sparse_tensor()?);
+	assert!(!coo.is_tensor()?);
+	let extracted = coo.extract_sparse_tensor::<f32>()?;
+	assert_eq!(extracted.format(), SparseFormat::Coo);
+	assert_eq!(extracted.dense_shape(), &[2, 3]);
+	assert_eq!(extracted.values(), &values);
+	assert_eq!(extracted.indices(), &SparseIndices::Coo(&[1, 3]));
+	assert!(matches!(coo.extract_sparse_tensor::<i64>(), Err(ort::Error::DataTypeMismatch { .. })));
+	assert!(matches!(coo.extract_sparse_tensor::<String>(), Err(ort::Error::SparseStringTensor)));
+
+	let csr = Value::from_sparse_tensor(&SparseTensor::csr([2, 3], &values, &[1, 0], &[0, 1, 2])?)?;
+	let extracted = csr.extract_sparse_tensor::<f32>()?;
+	assert_eq!(extracted.format(), SparseFormat::Csr);
+	assert_eq!(extracted.values(), &values);
+	assert_eq!(extracted.indices(), &SparseIndices::Csr { inner: &[1, 0], outer: &[0, 1, 2] });
+
+	// a 4x4 tensor with a single non-zero 2x2 block in the top right
+	let block = [1_i64, 2, 3, 4];
+	let block_sparse = Value::from_sparse_tensor(&SparseTensor::block_sparse([4, 4], [2, 2, 1], &block, [2, 1], &[0, 1])?)?;
+	let extracted = block_sparse.extract_sparse_tensor::<i64>()?;
+	assert_eq!(extracted.format(), SparseFormat::BlockSparse);
+	assert_eq!(extracted.values_shape(), &[2, 2, 1]);
+	assert_eq!(extracted.values(), &block);
+	assert_eq!(extracted.indices(), &SparseIndices::BlockSparse { shape: vec![2, 1], indices: &[0, 1] });
+
+	// shapes describing more elements than provided are rejected
+	assert!(matches!(SparseTensor::block_sparse([4, 4], [2, 2, 2], &block, [2, 1], &[0, 1]), Err(ort::Error::SparseTensorShapeMismatch { len: 4, .. })));
+	assert!(matches!(SparseTensor::block_sparse([4, 4], [2, 2, 1], &block, [2, 2], &[0, 1]), Err(ort::Error::SparseTensorShapeMismatch { len: 2, .. })));
+	assert!(matches!(SparseTensor::block_sparse([4, 4], [-2, -2, 1], &block, [2, 1], &[0, 1]), Err(ort::Error::SparseTensorShapeMismatch { .. })));
+
+	// COO & CSR indices must match the number of values & lie within the dense shape
+	assert!(SparseTensor::coo([2, 3], &values, &[0, 1, 1, 0]).is_ok());
+	assert!(matches!(SparseTensor::coo([2, 3], &values, &[1]), Err(ort::Error::SparseIndicesLengthMismatch { expected: 2, len: 1 })));
+	assert!(matches!(SparseTensor::coo([2, 3], &values, &[1, 6]), Err(ort::Error::SparseIndexOutOfBounds { index: 6, bound: 6 })));
+	assert!(matches!(SparseTensor::coo([2, 3], &values, &[0, 1, 2, 0]), Err(ort::Error::SparseIndexOutOfBounds { index: 2, bound: 2 })));
+	assert!(matches!(SparseTensor::coo([2, 3], &values, &[-1, 3]), Err(ort::Error::SparseIndexOutOfBounds { index: -1, .. })));
+	assert!(matches!(SparseTensor::csr([6], &values, &[1, 0], &[0, 2]), Err(ort::Error::InvalidSparseDenseShape(_))));
+	assert!(matches!(SparseTensor::csr([2, 3], &values, &[1], &[0, 1, 2]), Err(ort::Error::SparseIndicesLengthMismatch { expected: 2, len: 1 })));
+	assert!(matches!(SparseTensor::csr([2, 3], &values, &[1, 0], &[0, 2]), Err(ort::Error::SparseIndicesLengthMismatch { expected: 3, len: 2 })));
+	assert!(matches!(SparseTensor::csr([2, 3], &values, &[1, 3], &[0, 1, 2]), Err(ort::Error::SparseIndexOutOfBounds { index: 3, bound: 3 })));
+	assert!(matches!(SparseTensor::csr([2, 3], &values, &[1, 0], &[0, 2, 1]), Err(ort::Error::InvalidCsrOuterIndices(_))));
+	assert!(matches!(SparseTensor::csr([2, 3], &values, &[1, 0], &[0, 1, 3]), Err(ort::Error::InvalidCsrOuterIndices(_))));
+
+	let dense = Value::from_array(array![1.0_f32, 2.0])?;
+	assert!(!dense.is_sparse_tensor()?);
+	assert!(matches!(dense.extract_sparse_tensor::<f32>(), Err(ort::Error::ValueTypeMismatch { .. })));
+
+	Ok(())
+}
+
+#[test]
+fn sparse_matmul() -> ort::Result<()> {
+	// `sparse_matmul.onnx` multiplies a sparse matrix `A` with a dense matrix `B` using `SparseToDenseMatMul`.
+	let session = Session::builder()?.with_intra_threads(1)?.with_model_from_file(
+		Path::new(env!("CARGO_MANIFEST_DIR"))
+			.join("tests")
+			.join("data")
+			.join("sparse_matmul.onnx")
+	)?;
+	assert_eq!(
+		session.inputs[0].input_type,
+		ValueType::SparseTensor {
+			ty: TensorElementDataType::Float32,
+			dimensions: vec![-1, -1],
+			dimension_symbols: vec![Some("rows".to_owned()), Some("inner".to_owned())]
+		}
+	);
+	assert_eq!(session.inputs[0].input_type.tensor_shape(), Some(vec![Dim::Symbolic("rows".to_owned()), Dim::Symbolic("inner".to_owned())]));
+
+	// [[0, 1, 0], [2, 0, 0]]
+	let a = Value::from_sparse_tensor(&SparseTensor::coo([2, 3], &[1.0_f32, 2.0], &[1, 3])?)?;
+	let b = array![[1.0_f32, 2.0], [3.0, 4.0], [5.0, 6.0]];
+	let outputs = session.run(inputs!["A" => a, "B" => b]?)?;
+	assert_eq!(outputs["Y"].extract_raw_tensor::<f32>()?, (vec![2, 2], &[3.0, 4.0, 2.0, 4.0][..]));
+
+	Ok(())
+}
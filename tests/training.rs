@@ -0,0 +1,25 @@
+#![cfg(feature = "training")]
+
+use std::path::Path;
+
+use ort::Checkpoint;
+use test_log::test;
+
+#[test]
+fn checkpoint_missing_file() -> ort::Result<()> {
+	let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("checkpoint");
+	assert!(matches!(Checkpoint::load(&path), Err(ort::Error::FileDoesNotExist { filename }) if filename == path));
+
+	Ok(())
+}
+
+#[test]
+fn checkpoint_training_not_enabled() -> ort::Result<()> {
+	ort::init().with_name("integration_test").commit()?;
+
+	// the binaries used for testing are built without training support
+	let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("upsample.onnx");
+	assert!(matches!(Checkpoint::load(path), Err(ort::Error::TrainingNotEnabled)));
+
+	Ok(())
+}
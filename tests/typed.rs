@@ -0,0 +1,86 @@
+#![cfg(feature = "macros")]
+
+use std::path::Path;
+
+use ndarray::{Array, Array2, Array4, ArrayD};
+use ort::{GraphOptimizationLevel, Session, TensorElementDataType, TypedSession};
+use test_log::test;
+
+#[derive(ort::Inputs)]
+struct UpsampleInputs {
+	#[ort(name = "up_sampling2d_input:0")]
+	image: Array4<f32>
+}
+
+#[derive(ort::Outputs)]
+struct UpsampleOutputs {
+	#[ort(name = "Identity:0")]
+	image: ArrayD<f32>
+}
+
+#[derive(ort::Inputs)]
+struct WrongRankInputs {
+	#[ort(name = "up_sampling2d_input:0")]
+	image: Array2<f32>
+}
+
+#[derive(ort::Outputs)]
+struct WrongTypeOutputs {
+	#[ort(name = "Identity:0")]
+	#[allow(dead_code)]
+	image: ArrayD<i64>
+}
+
+#[derive(ort::Outputs)]
+struct WrongOutputs {
+	#[allow(dead_code)]
+	probabilities: ort::Value
+}
+
+fn upsample_session() -> ort::Result<Session> {
+	ort::init().with_name("integration_test").commit()?;
+
+	Session::builder()?
+		.with_optimization_level(GraphOptimizationLevel::Level1)?
+		.with_intra_threads(1)?
+		.with_model_from_file(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("upsample.onnx"))
+}
+
+#[test]
+fn typed_upsample() -> ort::Result<()> {
+	let session = TypedSession::<UpsampleInputs, UpsampleOutputs>::new(upsample_session()?)?;
+
+	let outputs = session.run(UpsampleInputs {
+		image: Array::zeros((1, 224, 224, 3))
+	})?;
+	assert_eq!(outputs.image.shape(), [1, 448, 448, 3]);
+
+	Ok(())
+}
+
+#[test]
+fn typed_signature_mismatch() -> ort::Result<()> {
+	let session = TypedSession::<UpsampleInputs, WrongOutputs>::new(upsample_session()?);
+	assert!(matches!(session, Err(ort::Error::UnknownOutput(name)) if name == "probabilities"));
+
+	Ok(())
+}
+
+#[test]
+fn typed_data_type_mismatch() -> ort::Result<()> {
+	let session = TypedSession::<UpsampleInputs, WrongTypeOutputs>::new(upsample_session()?);
+	assert!(matches!(
+		session,
+		Err(ort::Error::TypedDataTypeMismatch { name, expected: TensorElementDataType::Int64, .. }) if name == "Identity:0"
+	));
+
+	Ok(())
+}
+
+#[test]
+fn typed_rank_mismatch() -> ort::Result<()> {
+	let session = TypedSession::<WrongRankInputs, UpsampleOutputs>::new(upsample_session()?);
+	assert!(matches!(session, Err(ort::Error::TypedRankMismatch { name, expected: 2, actual: 4 }) if name == "up_sampling2d_input:0"));
+
+	Ok(())
+}
@@ -1,8 +1,17 @@
-use std::path::Path;
+use std::{
+	collections::HashMap,
+	future::Future,
+	path::Path,
+	pin::pin,
+	sync::Arc,
+	task::{Context, Poll, Wake},
+	thread::{self, Thread},
+	time::Duration
+};
 
 use image::RgbImage;
 use ndarray::{Array, CowArray, Ix4};
-use ort::{inputs, GraphOptimizationLevel, Session, Tensor};
+use ort::{inputs, BatchRunner, Dim, GraphOptimizationLevel, ModelFormat, PrepackedWeights, RunOptions, Session, SessionConfig, SessionPool, Tensor, Value};
 use test_log::test;
 
 fn load_input_image<P: AsRef<Path>>(name: P) -> RgbImage {
@@ -77,6 +86,27 @@ fn upsample() -> ort::Result<()> {
 	Ok(())
 }
 
+#[test]
+fn upsample_symbolic_dimensions() -> ort::Result<()> {
+	ort::init().with_name("integration_test").commit()?;
+
+	let session = Session::builder()?
+		.with_model_from_file(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("upsample.onnx"))
+		.expect("Could not load model");
+
+	assert_eq!(
+		session.inputs[0].input_type.tensor_shape().expect("input0 to be a tensor type"),
+		[
+			Dim::Symbolic("unk__31".to_owned()),
+			Dim::Symbolic("unk__32".to_owned()),
+			Dim::Symbolic("unk__33".to_owned()),
+			Dim::Fixed(3)
+		]
+	);
+
+	Ok(())
+}
+
 /// The upsample.ort can be produced by
 /// ```shell
 /// python -m onnxruntime.tools.convert_onnx_models_to_ort tests/data/upsample.onnx
@@ -113,3 +143,478 @@ fn upsample_with_ort_model() -> ort::Result<()> {
 
 	Ok(())
 }
+
+#[test]
+fn upsample_with_onnx_model_from_memory_directly() -> ort::Result<()> {
+	ort::init().with_name("integration_test").commit()?;
+
+	// `.onnx` models can't be loaded without copying, but are still accepted
+	let session_data =
+		std::fs::read(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("upsample.onnx")).expect("Could not open model from file");
+	let session = Session::builder()?.with_model_from_memory_directly(&session_data)?;
+	assert_eq!(session.inputs[0].input_type.tensor_dimensions().expect("input0 to be a tensor type"), &[-1, -1, -1, 3]);
+
+	Ok(())
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn upsample_with_mapped_model_requires_ort_format() -> ort::Result<()> {
+	ort::init().with_name("integration_test").commit()?;
+
+	let session = Session::builder()?.with_model_from_file_mapped(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("upsample.onnx"));
+	assert!(matches!(session, Err(ort::Error::NotOrtFormat)));
+
+	Ok(())
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn upsample_with_mapped_ort_model() -> ort::Result<()> {
+	const IMAGE_TO_LOAD: &str = "mushroom.png";
+
+	ort::init().with_name("integration_test").commit()?;
+
+	let session = Session::builder()?
+		.with_optimization_level(GraphOptimizationLevel::Level1)?
+		.with_intra_threads(1)?
+		.with_model_from_file_mapped(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("upsample.ort"))?;
+
+	let image_buffer = load_input_image(IMAGE_TO_LOAD);
+	let array = convert_image_to_cow_array(&image_buffer);
+	let outputs = session.run(inputs![&array]?)?;
+	let output: Tensor<f32> = outputs[0].extract_tensor()?;
+	assert_eq!(output.view().shape(), [1, 448, 448, 3]);
+
+	Ok(())
+}
+
+#[test]
+fn upsample_terminated() -> ort::Result<()> {
+	const IMAGE_TO_LOAD: &str = "mushroom.png";
+
+	ort::init().with_name("integration_test").commit()?;
+
+	let session = Session::builder()?
+		.with_optimization_level(GraphOptimizationLevel::Level1)?
+		.with_intra_threads(1)?
+		.with_model_from_file(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("upsample.onnx"))
+		.expect("Could not load model");
+
+	let image_buffer = load_input_image(IMAGE_TO_LOAD);
+	let array = convert_image_to_cow_array(&image_buffer);
+
+	let mut options = RunOptions::new()?;
+	options.set_tag("upsample_terminated")?;
+	assert_eq!(options.tag()?, "upsample_terminated");
+
+	let handle = options.handle();
+	std::thread::spawn(move || handle.terminate()).join().unwrap()?;
+	assert!(options.is_terminated());
+	assert!(matches!(session.run_with_options(inputs![&array]?, &options), Err(ort::Error::RunTerminated)));
+
+	options.unterminate()?;
+	let outputs = session.run_with_options(inputs![&array]?, &options)?;
+	let output: Tensor<f32> = outputs[0].extract_tensor()?;
+	assert_eq!(output.view().shape(), [1, 448, 448, 3]);
+
+	Ok(())
+}
+
+#[test]
+fn upsample_terminated_in_flight() -> ort::Result<()> {
+	const IMAGE_TO_LOAD: &str = "mushroom.png";
+
+	ort::init().with_name("integration_test").commit()?;
+
+	let session = Session::builder()?
+		.with_optimization_level(GraphOptimizationLevel::Level1)?
+		.with_intra_threads(1)?
+		.with_model_from_file(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("upsample.onnx"))
+		.expect("Could not load model");
+
+	let image_buffer = load_input_image(IMAGE_TO_LOAD);
+	let array = convert_image_to_cow_array(&image_buffer);
+
+	let options = RunOptions::new()?;
+	let handle = options.handle();
+	let error = thread::scope(|s| {
+		// keep running until the watchdog terminates a run; whichever run is aborted must report the termination
+		let runner = s.spawn(|| loop {
+			if let Err(e) = session.run_with_options(inputs![&array]?, &options) {
+				return Ok::<_, ort::Error>(e);
+			}
+		});
+		thread::sleep(Duration::from_millis(50));
+		handle.terminate()?;
+		runner.join().expect("runner thread panicked")
+	})?;
+	assert!(matches!(error, ort::Error::RunTerminated));
+
+	Ok(())
+}
+
+#[test]
+fn upsample_preallocated_output() -> ort::Result<()> {
+	const IMAGE_TO_LOAD: &str = "mushroom.png";
+
+	ort::init().with_name("integration_test").commit()?;
+
+	let session = Session::builder()?
+		.with_optimization_level(GraphOptimizationLevel::Level1)?
+		.with_intra_threads(1)?
+		.with_model_from_file(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("upsample.onnx"))
+		.expect("Could not load model");
+
+	let image_buffer = load_input_image(IMAGE_TO_LOAD);
+	let array = convert_image_to_cow_array(&image_buffer);
+
+	let output_name = session.outputs[0].name.clone();
+	let mut output = Value::from_array(Array::<f32, _>::zeros((1, 448, 448, 3)))?;
+	let buffer = output.extract_raw_tensor::<f32>()?.1.as_ptr();
+
+	for _ in 0..2 {
+		let mut outputs = session.run_with_outputs(inputs![&array]?, HashMap::from([(output_name.clone(), output)]))?;
+		assert_eq!(outputs.len(), 1);
+		output = outputs.remove(output_name.as_str()).expect("pre-allocated output should be handed back");
+		let (shape, data) = output.extract_raw_tensor::<f32>()?;
+		assert_eq!(shape, [1, 448, 448, 3]);
+		assert!(data.iter().any(|x| *x != 0.0));
+		// the output is written into the same buffer on every run
+		assert_eq!(data.as_ptr(), buffer);
+	}
+
+	Ok(())
+}
+
+#[test]
+fn upsample_async_preallocated_output() -> ort::Result<()> {
+	const IMAGE_TO_LOAD: &str = "mushroom.png";
+
+	ort::init().with_name("integration_test").commit()?;
+
+	let session = Session::builder()?
+		.with_optimization_level(GraphOptimizationLevel::Level1)?
+		.with_intra_threads(2)?
+		.with_model_from_file(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("upsample.onnx"))
+		.expect("Could not load model");
+
+	let image_buffer = load_input_image(IMAGE_TO_LOAD);
+	let array = convert_image_to_cow_array(&image_buffer);
+
+	let output_name = session.outputs[0].name.clone();
+	let mut output = Value::from_array(Array::<f32, _>::zeros((1, 448, 448, 3)))?;
+	let buffer = output.extract_raw_tensor::<f32>()?.1.as_ptr();
+
+	for _ in 0..2 {
+		let mut outputs = block_on(session.run_async_with_outputs(inputs![&array]?, HashMap::from([(output_name.clone(), output)])))?;
+		assert_eq!(outputs.len(), 1);
+		output = outputs.remove(output_name.as_str()).expect("pre-allocated output should be handed back");
+		let (shape, data) = output.extract_raw_tensor::<f32>()?;
+		assert_eq!(shape, [1, 448, 448, 3]);
+		assert!(data.iter().any(|x| *x != 0.0));
+		// the output is written into the same buffer on every run
+		assert_eq!(data.as_ptr(), buffer);
+	}
+
+	Ok(())
+}
+
+#[test]
+fn upsample_named_inputs() -> ort::Result<()> {
+	const IMAGE_TO_LOAD: &str = "mushroom.png";
+
+	ort::init().with_name("integration_test").commit()?;
+
+	let session = Session::builder()?
+		.with_optimization_level(GraphOptimizationLevel::Level1)?
+		.with_intra_threads(1)?
+		.with_model_from_file(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("upsample.onnx"))
+		.expect("Could not load model");
+
+	let image_buffer = load_input_image(IMAGE_TO_LOAD);
+	let array = convert_image_to_cow_array(&image_buffer);
+
+	let input_name = session.inputs[0].name.clone();
+	let outputs = session.run(inputs![input_name => &array]?)?;
+	let output: Tensor<f32> = outputs[0].extract_tensor()?;
+	assert_eq!(output.view().shape(), [1, 448, 448, 3]);
+
+	Ok(())
+}
+
+#[test]
+fn upsample_input_name_with_nul() -> ort::Result<()> {
+	ort::init().with_name("integration_test").commit()?;
+
+	let session = Session::builder()?
+		.with_input_validation(false)?
+		.with_intra_threads(1)?
+		.with_model_from_file(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("upsample.onnx"))
+		.expect("Could not load model");
+
+	let array = Array::<f32, _>::zeros((1, 224, 224, 3));
+	let input_name = format!("{}\0", session.inputs[0].name);
+	assert!(matches!(session.run(inputs![input_name => array]?), Err(ort::Error::FfiStringNull(_))));
+
+	Ok(())
+}
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+	fn wake(self: Arc<Self>) {
+		self.0.unpark();
+	}
+}
+
+fn block_on<F: Future>(fut: F) -> F::Output {
+	let mut fut = pin!(fut);
+	let waker = Arc::new(ThreadWaker(thread::current())).into();
+	let mut cx = Context::from_waker(&waker);
+	loop {
+		match fut.as_mut().poll(&mut cx) {
+			Poll::Ready(output) => return output,
+			Poll::Pending => thread::park()
+		}
+	}
+}
+
+#[test]
+fn upsample_async() -> ort::Result<()> {
+	const IMAGE_TO_LOAD: &str = "mushroom.png";
+
+	ort::init().with_name("integration_test").commit()?;
+
+	let session = Session::builder()?
+		.with_optimization_level(GraphOptimizationLevel::Level1)?
+		.with_intra_threads(2)?
+		.with_model_from_file(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("upsample.onnx"))
+		.expect("Could not load model");
+
+	let image_buffer = load_input_image(IMAGE_TO_LOAD);
+	let array = convert_image_to_cow_array(&image_buffer);
+
+	let outputs = block_on(session.run_async(inputs![&array]?))?;
+	let output: Tensor<f32> = outputs[0].extract_tensor()?;
+	assert_eq!(output.view().shape(), [1, 448, 448, 3]);
+
+	Ok(())
+}
+
+#[test]
+fn upsample_invalid_inputs() -> ort::Result<()> {
+	ort::init().with_name("integration_test").commit()?;
+
+	let session = Session::builder()?
+		.with_optimization_level(GraphOptimizationLevel::Level1)?
+		.with_intra_threads(1)?
+		.with_model_from_file(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("upsample.onnx"))
+		.expect("Could not load model");
+
+	let array = Array::<i64, _>::zeros((1, 224, 224, 3));
+	assert!(matches!(session.run(inputs![array]?), Err(ort::Error::InputDataTypeMismatch { .. })));
+
+	let array = Array::<f32, _>::zeros((224, 224, 3));
+	assert!(matches!(session.run(inputs![array]?), Err(ort::Error::InputShapeMismatch { .. })));
+
+	let array = Array::<f32, _>::zeros((1, 224, 224, 3));
+	assert!(matches!(session.run(inputs!["not_an_input" => array]?), Err(ort::Error::UnknownInput(_))));
+
+	Ok(())
+}
+
+#[test]
+fn upsample_batched() -> ort::Result<()> {
+	const IMAGE_TO_LOAD: &str = "mushroom.png";
+
+	ort::init().with_name("integration_test").commit()?;
+
+	let session = Session::builder()?
+		.with_optimization_level(GraphOptimizationLevel::Level1)?
+		.with_intra_threads(1)?
+		.with_model_from_file(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("upsample.onnx"))
+		.expect("Could not load model");
+	let runner = BatchRunner::builder(Arc::new(session))
+		.with_max_batch_size(4)
+		.with_max_wait(Duration::from_millis(50))
+		.build()?;
+
+	let image_buffer = load_input_image(IMAGE_TO_LOAD);
+	let array = convert_image_to_cow_array(&image_buffer).into_owned();
+
+	thread::scope(|s| {
+		let handles: Vec<_> = (0..4)
+			.map(|i| {
+				let runner = &runner;
+				let array = array.mapv(|x| x + i as f32);
+				s.spawn(move || -> ort::Result<()> {
+					let expected = runner.session().run(inputs![array.view()]?)?;
+					let expected: Tensor<f32> = expected[0].extract_tensor()?;
+
+					let outputs = runner.run(inputs![array]?)?;
+					let output: Tensor<f32> = outputs[0].extract_tensor()?;
+					assert_eq!(output.view().shape(), [1, 448, 448, 3]);
+					assert_eq!(*output.view(), *expected.view());
+					Ok(())
+				})
+			})
+			.collect();
+		handles.into_iter().try_for_each(|handle| handle.join().unwrap())
+	})?;
+
+	Ok(())
+}
+
+#[test]
+fn upsample_pool() -> ort::Result<()> {
+	const IMAGE_TO_LOAD: &str = "mushroom.png";
+
+	ort::init().with_name("integration_test").commit()?;
+
+	let builder = Session::builder()?
+		.with_optimization_level(GraphOptimizationLevel::Level1)?
+		.with_intra_threads(1)?;
+	let pool = SessionPool::from_file(builder, Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("upsample.onnx"), 2)?;
+	assert_eq!(pool.size(), 2);
+	assert_eq!(pool.in_use(), 0);
+
+	let image_buffer = load_input_image(IMAGE_TO_LOAD);
+	let array = convert_image_to_cow_array(&image_buffer);
+
+	{
+		let _first = pool.get();
+		let _second = pool.try_get().expect("pool should have a second session");
+		assert_eq!(pool.utilization(), 1.0);
+		assert!(pool.try_get().is_none());
+	}
+	assert_eq!(pool.in_use(), 0);
+
+	thread::scope(|s| {
+		let handles: Vec<_> = (0..4)
+			.map(|_| {
+				s.spawn(|| -> ort::Result<()> {
+					let outputs = pool.run(inputs![&array]?)?;
+					let output: Tensor<f32> = outputs[0].extract_tensor()?;
+					assert_eq!(output.view().shape(), [1, 448, 448, 3]);
+					Ok(())
+				})
+			})
+			.collect();
+		handles.into_iter().try_for_each(|handle| handle.join().unwrap())
+	})?;
+
+	Ok(())
+}
+
+#[test]
+fn upsample_prepacked_weights() -> ort::Result<()> {
+	const IMAGE_TO_LOAD: &str = "mushroom.png";
+
+	ort::init().with_name("integration_test").commit()?;
+
+	let weights = PrepackedWeights::new()?;
+	let sessions = (0..2)
+		.map(|_| {
+			Session::builder()?
+				.with_optimization_level(GraphOptimizationLevel::Level1)?
+				.with_prepacked_weights(&weights)?
+				.with_model_from_file(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("upsample.onnx"))
+		})
+		.collect::<ort::Result<Vec<_>>>()?;
+	// sessions keep the container alive
+	drop(weights);
+
+	let image_buffer = load_input_image(IMAGE_TO_LOAD);
+	let array = convert_image_to_cow_array(&image_buffer);
+	for session in &sessions {
+		let outputs = session.run(inputs![&array]?)?;
+		let output: Tensor<f32> = outputs[0].extract_tensor()?;
+		assert_eq!(output.view().shape(), [1, 448, 448, 3]);
+	}
+
+	Ok(())
+}
+
+#[test]
+fn upsample_optimize_offline() -> ort::Result<()> {
+	const IMAGE_TO_LOAD: &str = "mushroom.png";
+
+	ort::init().with_name("integration_test").commit()?;
+
+	let image_buffer = load_input_image(IMAGE_TO_LOAD);
+	let array = convert_image_to_cow_array(&image_buffer);
+
+	for (format, extension) in [(ModelFormat::Onnx, "onnx"), (ModelFormat::Ort, "ort")] {
+		let optimized_path = std::env::temp_dir().join(format!("ort-upsample-optimized.{extension}"));
+		ort::optimize_model(
+			Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("upsample.onnx"),
+			&optimized_path,
+			GraphOptimizationLevel::Level1,
+			format
+		)?;
+		assert!(optimized_path.exists());
+
+		{
+			let session = Session::builder()?
+				.with_optimization_level(GraphOptimizationLevel::Disable)?
+				.with_model_from_file(&optimized_path)?;
+			let outputs = session.run(inputs![&array]?)?;
+			let output: Tensor<f32> = outputs[0].extract_tensor()?;
+			assert_eq!(output.view().shape(), [1, 448, 448, 3]);
+		}
+
+		let _ = std::fs::remove_file(optimized_path);
+	}
+
+	Ok(())
+}
+
+#[test]
+fn upsample_config_entries() -> ort::Result<()> {
+	const IMAGE_TO_LOAD: &str = "mushroom.png";
+
+	ort::init().with_name("integration_test").commit()?;
+
+	let builder = Session::builder()?
+		.with_config(SessionConfig::IntraOpAllowSpinning(false))?
+		.with_config_entry("session.disable_prepacking", "1")?;
+	assert_eq!(builder.config_entry("session.intra_op.allow_spinning")?.as_deref(), Some("0"));
+	assert_eq!(builder.config_entry(SessionConfig::DisablePrepacking(true).key())?.as_deref(), Some("1"));
+	assert_eq!(builder.config_entry("session.use_env_allocators")?, None);
+
+	let session = builder.with_model_from_file(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("upsample.onnx"))?;
+	let image_buffer = load_input_image(IMAGE_TO_LOAD);
+	let array = convert_image_to_cow_array(&image_buffer);
+	let outputs = session.run(inputs![&array]?)?;
+	let output: Tensor<f32> = outputs[0].extract_tensor()?;
+	assert_eq!(output.view().shape(), [1, 448, 448, 3]);
+
+	Ok(())
+}
+
+#[test]
+fn upsample_dimension_override() -> ort::Result<()> {
+	const IMAGE_TO_LOAD: &str = "mushroom.png";
+
+	ort::init().with_name("integration_test").commit()?;
+
+	// `unk__31` is the symbolic batch dimension of the model's input
+	let session = Session::builder()?
+		.with_dimension_override_by_name("unk__31", 1)?
+		.with_dimension_override("DATA_BATCH", 1)?
+		.with_model_from_file(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("upsample.onnx"))?;
+	assert_eq!(session.inputs[0].input_type.tensor_shape().map(|dims| dims[0].clone()), Some(Dim::Fixed(1)));
+
+	let image_buffer = load_input_image(IMAGE_TO_LOAD);
+	let array = convert_image_to_cow_array(&image_buffer);
+	let outputs = session.run(inputs![&array]?)?;
+	let output: Tensor<f32> = outputs[0].extract_tensor()?;
+	assert_eq!(output.view().shape(), [1, 448, 448, 3]);
+
+	// the batch dimension is no longer dynamic, so a batch of 2 is rejected
+	let batch = Array::<f32, _>::zeros((2, 224, 224, 3));
+	assert!(session.run(inputs![batch]?).is_err());
+
+	Ok(())
+}